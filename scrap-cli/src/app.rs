@@ -1,7 +1,7 @@
 use crate::app_error::AppError;
-use crate::cli::{CliCommand, ItemKind};
+use crate::cli::{CliCommand, ItemKind, TagCommand, TrashCommand};
 
-use scrap::api::{FolderSummary, NoteSummary};
+use scrap::api::{FolderNode, FolderSummary, NoteDetail, NoteSummary, PurgeReport, SkipReason};
 use scrap::{Scrap, ScrapError};
 use std::io::Read;
 use std::path::Path;
@@ -39,10 +39,54 @@ impl App {
             .map_err(|err| AppError::WorkspaceInitializationFailed(format!("{:?}", err)))?;
 
         // Sync workspace
-        self.scrap
+        let report = self
+            .scrap
             .sync_workspace()
             .map_err(|err| AppError::WorkspaceSyncFailed(format!("{:?}", err)))?;
 
+        for skipped in report.scan.skipped {
+            let reason = match skipped.reason {
+                SkipReason::PermissionDenied => "permission denied".to_string(),
+                SkipReason::InvalidEncoding => "not valid UTF-8".to_string(),
+                SkipReason::MalformedFrontMatter => "malformed front matter".to_string(),
+                SkipReason::Unreadable(err) => format!("unreadable ({})", err),
+            };
+
+            eprintln!("Warning: Skipped '{}': {}.", skipped.path.display(), reason);
+        }
+
+        for unsaved in report.scan.unsaved_ids {
+            let kind = if unsaved.is_folder { "folder" } else { "note" };
+
+            eprintln!(
+                "Warning: Could not write the ID of {} '{}' ({}), it will get a new ID on the next sync.",
+                kind,
+                unsaved.path.display(),
+                unsaved.error
+            );
+        }
+
+        for conflict in report.id_conflicts {
+            let kind = if conflict.is_folder { "Folder" } else { "Note" };
+
+            match conflict.new_id {
+                Some(new_id) => eprintln!(
+                    "{} '{}' had the same ID as '{}', it was given the new ID {}.",
+                    kind,
+                    conflict.duplicate_path.display(),
+                    conflict.kept_path.display(),
+                    new_id
+                ),
+                None => eprintln!(
+                    "Warning: {} '{}' was skipped, its ID {} is already used by '{}'.",
+                    kind,
+                    conflict.duplicate_path.display(),
+                    conflict.id,
+                    conflict.kept_path.display()
+                ),
+            }
+        }
+
         // Update memory
         match self.scrap.list_notes() {
             Ok(notes) => {
@@ -78,6 +122,18 @@ impl App {
             CliCommand::Remove { kind, id } => self.handle_remove(kind, id),
 
             CliCommand::NewFolder { name, parent } => self.handle_new_folder(name, parent),
+
+            CliCommand::Search { query, page } => self.handle_search(query, page),
+
+            CliCommand::Query { query } => self.handle_query(query),
+
+            CliCommand::Tree => self.handle_tree(),
+
+            CliCommand::Trash { command } => self.handle_trash(command),
+
+            CliCommand::Tag { command } => self.handle_tag(command),
+
+            CliCommand::Links { id } => self.handle_links(id),
         }
     }
 
@@ -104,27 +160,15 @@ impl App {
             return;
         }
 
-        // if let Some(id) = ids.get(0) {
-        //     match self.scrap.handle_command(ScrapCommand::GetNoteBody(*id)) {
-        //         Ok(event) => {
-        //             //
-        //             match event {
-        //                 ScrapEvent::NoteBody(body) => print_note(body),
-        //                 _ => {
-        //                     // Unexpected returned event
-        //                     print!("Ambiguous Error: Unmatched Scrap event OR Scrap error.")
-        //                 }
-        //             }
-        //         }
-        //         Err(ScrapError::NoteNotFound(err_id)) => {
-        //             // Our ids are cached correctly
-        //             print!("Internal Error: Cached id '{}' does not exists anymore.", id);
-        //         }
-        //         Err(err) => {
-        //             // TODO: Handle scrap errors
-        //         }
-        //     }
-        // }
+        let id = ids.first().unwrap();
+        match self.scrap.get_note(*id) {
+            Ok(note) => print_note(&note),
+            Err(ScrapError::NoteNotFound(_)) => {
+                // Our ids are cached from the last sync, so this should not happen
+                eprintln!("Internal Error: Cached id '{}' does not exist anymore.", id);
+            }
+            Err(err) => eprintln!("Failed to open note with error: {:?}", err),
+        }
     }
 
     fn handle_add(self: &mut Self, title: String, file_type: String, parent: String) {
@@ -222,6 +266,187 @@ impl App {
         }
     }
 
+    fn handle_search(self: &mut Self, query: String, page: usize) {
+        const PAGE_SIZE: usize = 20;
+
+        let offset = page.saturating_sub(1).saturating_mul(PAGE_SIZE);
+        let hits = match self.scrap.search(&query, offset, PAGE_SIZE) {
+            Ok(hits) => hits,
+            Err(err) => {
+                eprintln!("Failed to search with error: {:?}", err);
+                return;
+            }
+        };
+
+        if hits.is_empty() {
+            println!("No notes found matching '{}'.", query);
+            return;
+        }
+
+        let is_page_full = hits.len() == PAGE_SIZE;
+
+        for hit in hits {
+            println!("{} {}", &hit.id.to_string()[..6], hit.title);
+            println!("    {}", hit.snippet);
+        }
+
+        if is_page_full {
+            println!("More hits may follow, use --page {} to see them.", page.max(1) + 1);
+        }
+    }
+
+    fn handle_query(self: &mut Self, query: String) {
+        let notes = match self.scrap.query(&query) {
+            Ok(notes) => notes,
+            Err(ScrapError::InvalidQuery(err)) => {
+                eprintln!("Invalid query: {:?}", err);
+                eprintln!("    {}", query);
+                eprintln!("    {}^", " ".repeat(err.position()));
+                return;
+            }
+            Err(err) => {
+                eprintln!("Failed to query with error: {:?}", err);
+                return;
+            }
+        };
+
+        if notes.is_empty() {
+            println!("No notes found matching '{}'.", query);
+            return;
+        }
+
+        for note in notes {
+            println!("{} {} ({})", &note.id.to_string()[..6], note.title, note.file_type);
+        }
+    }
+
+    fn handle_tree(self: &mut Self) {
+        match self.scrap.tree() {
+            Ok(root) => print_tree(&root, 0),
+            Err(err) => eprintln!("Failed to build workspace tree with error: {:?}", err),
+        }
+    }
+
+    fn handle_trash(self: &mut Self, command: TrashCommand) {
+        let result = match command {
+            TrashCommand::Empty => self.scrap.empty_trash(),
+            TrashCommand::Purge { older_than: Some(age) } => self.scrap.purge_trash(age),
+            TrashCommand::Purge { older_than: None } => {
+                if self.scrap.get_trash_retention_days().is_none() {
+                    eprintln!("Error: No retention period is set for this workspace, use --older-than.");
+                    return;
+                }
+
+                self.scrap.purge_expired_trash()
+            }
+        };
+
+        match result {
+            Ok(report) => print_purge_report(&report),
+            Err(ScrapError::InvalidRetentionDays(days)) => {
+                eprintln!(
+                    "Error: The retention period of {} days is too long, use --older-than.",
+                    days
+                );
+            }
+            Err(err) => eprintln!("Failed to purge trash with error: {:?}", err),
+        }
+    }
+
+    fn handle_tag(self: &mut Self, command: TagCommand) {
+        let (id, tag, is_add) = match command {
+            TagCommand::Add { id, tag } => (id, tag, true),
+            TagCommand::Remove { id, tag } => (id, tag, false),
+            TagCommand::List { tag: Some(tag) } => {
+                match self.scrap.list_notes_by_tag(&tag) {
+                    Ok(notes) if notes.is_empty() => println!("No notes tagged '{}'.", tag),
+                    Ok(notes) => {
+                        for note in notes {
+                            println!("{} {}", &note.id.to_string()[..6], note.title);
+                        }
+                    }
+                    Err(err) => eprintln!("Failed to list notes with error: {:?}", err),
+                }
+                return;
+            }
+            TagCommand::List { tag: None } => {
+                for tag in self.scrap.list_tags().unwrap_or_default() {
+                    println!("{}", tag);
+                }
+                return;
+            }
+        };
+
+        let ids = self.resolve_note_id(&id);
+
+        if ids.len() != 1 {
+            eprintln!(
+                "Error: Expected exactly one note matching '{}', found {}.",
+                id,
+                ids.len()
+            );
+            return;
+        }
+
+        let result = if is_add {
+            self.scrap.add_tag(ids[0], &tag)
+        } else {
+            self.scrap.remove_tag(ids[0], &tag)
+        };
+
+        if let Err(err) = result.and_then(|_| self.scrap.save_note(ids[0])) {
+            eprintln!("Failed to update tags with error: {:?}", err);
+        }
+    }
+
+    fn handle_links(self: &mut Self, id: Option<String>) {
+        let Some(id) = id else {
+            for link in self.scrap.broken_links().unwrap_or_default() {
+                println!(
+                    "{} {} -> [[{}]]",
+                    &link.source.id.to_string()[..6],
+                    link.source.title,
+                    link.target
+                );
+            }
+            return;
+        };
+
+        let ids = self.resolve_note_id(&id);
+
+        if ids.len() != 1 {
+            eprintln!(
+                "Error: Expected exactly one note matching '{}', found {}.",
+                id,
+                ids.len()
+            );
+            return;
+        }
+
+        match self.scrap.outgoing_links(ids[0]) {
+            Ok(links) => {
+                println!("Links:");
+                for link in links {
+                    match link.note {
+                        Some(note) => println!("    {} {}", &note.id.to_string()[..6], note.title),
+                        None => println!("    ------ [[{}]] (broken)", link.target),
+                    }
+                }
+            }
+            Err(err) => eprintln!("Failed to get links with error: {:?}", err),
+        }
+
+        match self.scrap.backlinks(ids[0]) {
+            Ok(notes) => {
+                println!("Backlinks:");
+                for note in notes {
+                    println!("    {} {}", &note.id.to_string()[..6], note.title);
+                }
+            }
+            Err(err) => eprintln!("Failed to get backlinks with error: {:?}", err),
+        }
+    }
+
     fn insert_note(self: &mut Self, note: NoteSummary) {
         let id: Uuid = note.id;
 
@@ -259,7 +484,18 @@ impl App {
             }
         }
 
-        return Vec::new();
+        // Fall back to title matching, keeping only the best ranked notes
+        let matches = self.scrap.find_notes_by_title(input).unwrap_or_default();
+
+        let Some(best_score) = matches.first().map(|m| m.score) else {
+            return Vec::new();
+        };
+
+        return matches
+            .iter()
+            .take_while(|m| m.score == best_score)
+            .map(|m| m.note.id)
+            .collect();
     }
 
     fn resolve_folder_id(self: &Self, input: &str) -> Vec<Uuid> {
@@ -289,12 +525,61 @@ impl App {
     }
 }
 
-fn print_note(body: String) {
+fn print_tree(node: &FolderNode, depth: usize) {
+    let indent = "  ".repeat(depth);
+
+    if depth == 0 {
+        println!("📂 /");
+    } else {
+        println!(
+            "{}📂 {} ({}) [{} notes, {} folders]",
+            indent,
+            node.display_name,
+            &node.id.to_string()[..6],
+            node.note_count,
+            node.folder_count
+        );
+    }
+
+    for folder in &node.folders {
+        print_tree(folder, depth + 1);
+    }
+
+    for note in &node.notes {
+        println!("{}  📝 {} ({})", indent, note.title, &note.id.to_string()[..6]);
+    }
+}
+
+fn print_purge_report(report: &PurgeReport) {
+    let deleted_count = report.deleted.len() + report.untracked_paths.len();
+    println!("Deleted {} item(s) from trash.", deleted_count);
+
+    for item in &report.deleted {
+        println!("  {}: {}", item.id, item.name);
+    }
+
+    for path in &report.untracked_paths {
+        println!("  {}", path.display());
+    }
+
+    if !report.failed_ids.is_empty() {
+        eprintln!(
+            "Failed to delete {} item(s), they are kept in the trash:",
+            report.failed_ids.len()
+        );
+
+        for id in &report.failed_ids {
+            eprintln!("  {}", id);
+        }
+    }
+}
+
+fn print_note(note: &NoteDetail) {
     // Clanker made code ahead! 🤖
 
-    // let title = self.metadata.get_title();
-    // let file_type = self.metadata.get_file_type();
-    // let id = self.metadata.get_id().to_string();
+    let title = &note.title;
+    let file_type = &note.file_type;
+    let id = note.id.to_string();
 
     let cyan = "\x1b[38;5;213m";
     let gray = "\x1b[90m";
@@ -306,25 +591,40 @@ fn print_note(body: String) {
 
     println!("{gray}╭{}╮{reset}", horiz);
 
-    let title_line = format!("{:<width$}", "Title", width = width - 5);
+    let title_line = format!("{:<width$}", title, width = width - 5);
     println!("{gray}│{reset} 📝 {cyan}{bold}{}{reset} {gray}│{reset}", title_line);
 
     println!("{gray}├{}┤{reset}", horiz);
 
-    // // Front matter
-    // let id_line = format!("{:<width$}", id, width = width - 8);
-    // println!("{gray}│{reset} {gray}ID:   {reset}{} {gray}│{reset}", id_line);
+    // Front matter
+    let id_line = format!("{:<width$}", id, width = width - 8);
+    println!("{gray}│{reset} {gray}ID:   {reset}{} {gray}│{reset}", id_line);
+
+    let type_line = format!("{:<width$}", file_type, width = width - 8);
+    println!(
+        "{gray}│{reset} {gray}TYPE: {reset}{bold}{}{reset} {gray}│{reset}",
+        type_line
+    );
 
-    // let type_line = format!("{:<width$}", file_type, width = width - 8);
-    // println!(
-    //     "{gray}│{reset} {gray}TYPE: {reset}{bold}{}{reset} {gray}│{reset}",
-    //     type_line
-    // );
+    let path_line = format!("{:<width$}", note.relative_path.display(), width = width - 8);
+    println!("{gray}│{reset} {gray}PATH: {reset}{} {gray}│{reset}", path_line);
+
+    if !note.tags.is_empty() {
+        let tags_line = format!("{:<width$}", note.tags.join(", "), width = width - 8);
+        println!("{gray}│{reset} {gray}TAGS: {reset}{} {gray}│{reset}", tags_line);
+    }
 
-    // println!("{gray}├{}┤{reset}", horiz);
+    // Front matter keys from other tools, multi-line values are shown on one line
+    for (key, value) in &note.properties {
+        let property = format!("{}: {}", key, value.split_whitespace().collect::<Vec<_>>().join(" "));
+        let property_line = format!("{:<width$}", property, width = width - 2);
+        println!("{gray}│{reset} {} {gray}│{reset}", property_line);
+    }
+
+    println!("{gray}├{}┤{reset}", horiz);
 
     // Body
-    for line in body.lines() {
+    for line in note.body.lines() {
         let content_line = format!("{:<width$}", line, width = width - 2);
         println!("{gray}│{reset} {} {gray}│{reset}", content_line);
     }
@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand};
+use std::time::Duration;
 
 #[derive(Parser)]
 pub struct CliArgs {
@@ -31,6 +32,87 @@ pub enum CliCommand {
         #[arg(short, long, default_value = "3e206920-6c75-7620-7520-6d722063656f")]
         parent: String,
     },
+
+    /// Search note titles and bodies
+    Search {
+        query: String,
+        /// Page of hits to print, starting from 1
+        #[arg(short, long, default_value_t = 1)]
+        page: usize,
+    },
+
+    /// Select notes with a query, e.g. `type:markdown in:work -draft created:>2026-01-01`
+    Query {
+        query: String,
+    },
+
+    /// Print the workspace as a tree of folders and notes
+    Tree,
+
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommand,
+    },
+
+    Tag {
+        #[command(subcommand)]
+        command: TagCommand,
+    },
+
+    /// Print a note's outgoing links and backlinks, or every broken link when no note is given
+    Links {
+        id: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagCommand {
+    /// Tag a note, tags nest with `/` like `project/scrap`
+    Add { id: String, tag: String },
+
+    /// Remove a tag from a note
+    Remove { id: String, tag: String },
+
+    /// List notes with the given tag or any tag nested below it, or every tag when none is given
+    List { tag: Option<String> },
+}
+
+#[derive(Subcommand)]
+pub enum TrashCommand {
+    /// Permanently delete everything in the trash
+    Empty,
+
+    /// Permanently delete trashed items older than the given age, e.g. `30d`.
+    /// Falls back to the workspace's retention period when no age is given.
+    Purge {
+        #[arg(long, value_parser = parse_age)]
+        older_than: Option<Duration>,
+    },
+}
+
+/// Parses an age such as `45s`, `30m`, `12h`, `30d` or `2w`.
+fn parse_age(input: &str) -> Result<Duration, String> {
+    let unit_start = input
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or(format!("Missing unit in '{}', expected one of s, m, h, d, w", input))?;
+    let (amount, unit) = input.split_at(unit_start);
+
+    let amount: u64 = amount.parse().map_err(|_| format!("Invalid amount in '{}'", input))?;
+
+    let seconds_per_unit = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(format!("Unknown unit '{}', expected one of s, m, h, d, w", unit)),
+    };
+
+    let seconds = amount
+        .checked_mul(seconds_per_unit)
+        .ok_or(format!("Age '{}' is too large", input))?;
+
+    return Ok(Duration::from_secs(seconds));
 }
 
 #[derive(clap::ValueEnum, Clone)]
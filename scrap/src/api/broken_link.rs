@@ -0,0 +1,17 @@
+use crate::api::NoteSummary;
+
+pub struct BrokenLink {
+    /// The note containing the link
+    pub source: NoteSummary,
+    /// Link target as written in the body, a note title or ID that doesn't exist
+    pub target: String,
+}
+
+impl BrokenLink {
+    pub fn new(source: NoteSummary, target: impl Into<String>) -> Self {
+        return Self {
+            source,
+            target: target.into(),
+        };
+    }
+}
@@ -0,0 +1,44 @@
+use crate::api::NoteSummary;
+
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// A folder within the workspace tree along with its child notes and folders.
+pub struct FolderNode {
+    pub id: Uuid,
+    pub display_name: String,
+    /// `Uuid::nil()` for the workspace root
+    pub parent_id: Uuid,
+    pub relative_path: PathBuf,
+    pub note_count: usize,
+    pub folder_count: usize,
+    /// Metadata keys from other tools and their values, in the order they were written
+    pub properties: Vec<(String, String)>,
+    pub notes: Vec<NoteSummary>,
+    /// Empty when the tree was not expanded down to this folder's children
+    pub folders: Vec<FolderNode>,
+}
+
+impl FolderNode {
+    pub fn new(
+        id: Uuid,
+        display_name: impl Into<String>,
+        parent_id: Uuid,
+        relative_path: impl Into<PathBuf>,
+        note_count: usize,
+        folder_count: usize,
+        properties: Vec<(String, String)>,
+    ) -> Self {
+        return Self {
+            id,
+            display_name: display_name.into(),
+            parent_id,
+            relative_path: relative_path.into(),
+            note_count,
+            folder_count,
+            properties,
+            notes: Vec::new(),
+            folders: Vec::new(),
+        };
+    }
+}
@@ -3,13 +3,15 @@ use uuid::Uuid;
 pub struct FolderSummary {
     pub id: Uuid,
     pub display_name: String,
+    pub parent_id: Uuid,
 }
 
 impl FolderSummary {
-    pub fn new(id: Uuid, display_name: impl Into<String>) -> FolderSummary {
+    pub fn new(id: Uuid, display_name: impl Into<String>, parent_id: Uuid) -> FolderSummary {
         return Self {
             id,
             display_name: display_name.into(),
+            parent_id,
         };
     }
 }
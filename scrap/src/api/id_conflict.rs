@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// A note or folder found during sync with an ID already used by another one.
+pub struct IdConflict {
+    pub id: Uuid,
+    pub is_folder: bool,
+    /// The item that kept the ID
+    pub kept_path: PathBuf,
+    /// The duplicate, at its new path if it was given a new ID
+    pub duplicate_path: PathBuf,
+    /// The duplicate's new ID, `None` if it couldn't be rewritten and was left out of the workspace
+    pub new_id: Option<Uuid>,
+}
+
+impl IdConflict {
+    pub fn new(id: Uuid, is_folder: bool, kept_path: PathBuf, duplicate_path: PathBuf, new_id: Option<Uuid>) -> Self {
+        return Self {
+            id,
+            is_folder,
+            kept_path,
+            duplicate_path,
+            new_id,
+        };
+    }
+}
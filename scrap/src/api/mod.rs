@@ -1,11 +1,41 @@
 mod scrap;
 mod scrap_error;
 
+mod broken_link;
+mod folder_node;
 mod folder_summary;
+mod id_conflict;
+mod note_detail;
+mod note_link;
 mod note_summary;
+mod purge_report;
+mod query_error;
+mod scan_report;
+mod search_hit;
+mod skip_reason;
+mod skipped_file;
+mod sync_report;
+mod title_match;
+mod trash_summary;
+mod unsaved_id;
 
 pub use scrap::Scrap;
 pub use scrap_error::ScrapError;
 
+pub use broken_link::BrokenLink;
+pub use folder_node::FolderNode;
 pub use folder_summary::FolderSummary;
+pub use id_conflict::IdConflict;
+pub use note_detail::NoteDetail;
+pub use note_link::NoteLink;
 pub use note_summary::NoteSummary;
+pub use purge_report::PurgeReport;
+pub use query_error::QueryError;
+pub use scan_report::ScanReport;
+pub use search_hit::SearchHit;
+pub use skip_reason::SkipReason;
+pub use skipped_file::SkippedFile;
+pub use sync_report::SyncReport;
+pub use title_match::TitleMatch;
+pub use trash_summary::TrashSummary;
+pub use unsaved_id::UnsavedId;
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+use uuid::Uuid;
+
+pub struct NoteDetail {
+    pub id: Uuid,
+    pub title: String,
+    pub file_type: String,
+    pub folder_id: Uuid,
+    pub relative_path: PathBuf,
+    pub tags: Vec<String>,
+    /// Front matter keys from other tools and their values, in the order they were written
+    pub properties: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl NoteDetail {
+    pub fn new(
+        id: Uuid,
+        title: impl Into<String>,
+        file_type: impl Into<String>,
+        folder_id: Uuid,
+        relative_path: impl Into<PathBuf>,
+        tags: Vec<String>,
+        properties: Vec<(String, String)>,
+        body: impl Into<String>,
+    ) -> Self {
+        return Self {
+            id,
+            title: title.into(),
+            file_type: file_type.into(),
+            folder_id,
+            relative_path: relative_path.into(),
+            tags,
+            properties,
+            body: body.into(),
+        };
+    }
+}
@@ -0,0 +1,17 @@
+use crate::api::NoteSummary;
+
+pub struct NoteLink {
+    /// Link target as written in the body, a note title or ID
+    pub target: String,
+    /// The linked note, `None` if the link is broken
+    pub note: Option<NoteSummary>,
+}
+
+impl NoteLink {
+    pub fn new(target: impl Into<String>, note: Option<NoteSummary>) -> Self {
+        return Self {
+            target: target.into(),
+            note,
+        };
+    }
+}
@@ -4,14 +4,16 @@ pub struct NoteSummary {
     pub id: Uuid,
     pub title: String,
     pub file_type: String,
+    pub folder_id: Uuid,
 }
 
 impl NoteSummary {
-    pub fn new(id: Uuid, title: impl Into<String>, file_type: impl Into<String>) -> Self {
+    pub fn new(id: Uuid, title: impl Into<String>, file_type: impl Into<String>, folder_id: Uuid) -> Self {
         return Self {
             id,
             title: title.into(),
             file_type: file_type.into(),
+            folder_id,
         };
     }
 }
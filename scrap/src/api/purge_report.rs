@@ -0,0 +1,22 @@
+use crate::api::TrashSummary;
+
+use std::path::PathBuf;
+use uuid::Uuid;
+
+pub struct PurgeReport {
+    pub deleted: Vec<TrashSummary>,
+    /// Items that could not be deleted, they are kept in the trash
+    pub failed_ids: Vec<Uuid>,
+    /// Files and folders found in the trash that were never recorded in the trash manifest
+    pub untracked_paths: Vec<PathBuf>,
+}
+
+impl PurgeReport {
+    pub fn new(deleted: Vec<TrashSummary>, failed_ids: Vec<Uuid>, untracked_paths: Vec<PathBuf>) -> Self {
+        return Self {
+            deleted,
+            failed_ids,
+            untracked_paths,
+        };
+    }
+}
@@ -0,0 +1,20 @@
+/// Error found while parsing a query, positions are char offsets into the query string.
+#[derive(Debug)]
+pub enum QueryError {
+    /// A `"` without its closing `"`
+    UnterminatedQuote(usize),
+    /// A `-` or `field:` with nothing after it
+    MissingValue(usize),
+    /// A date that is not in the `YYYY-MM-DD` format or doesn't exist, like `2026-02-31`
+    InvalidDate(usize, String),
+}
+
+impl QueryError {
+    pub fn position(self: &Self) -> usize {
+        match self {
+            Self::UnterminatedQuote(position) => return *position,
+            Self::MissingValue(position) => return *position,
+            Self::InvalidDate(position, _) => return *position,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::api::{SkippedFile, UnsavedId};
+
+#[derive(Default)]
+pub struct ScanReport {
+    /// Skipped directories are listed once, without the files inside them
+    pub skipped: Vec<SkippedFile>,
+    /// Loaded notes whose generated ID couldn't be written
+    pub unsaved_ids: Vec<UnsavedId>,
+}
+
+impl ScanReport {
+    pub fn new() -> Self {
+        return Self {
+            skipped: Vec::new(),
+            unsaved_ids: Vec::new(),
+        };
+    }
+}
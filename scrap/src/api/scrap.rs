@@ -1,7 +1,11 @@
-use crate::api::{FolderSummary, NoteSummary, ScrapError};
+use crate::api::{
+    BrokenLink, FolderNode, FolderSummary, NoteDetail, NoteLink, NoteSummary, PurgeReport, ScrapError, SearchHit,
+    SyncReport, TitleMatch, TrashSummary,
+};
 use crate::app::{App, AppEvent};
 
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use uuid::Uuid;
 
 pub struct Scrap {
@@ -19,8 +23,13 @@ impl Scrap {
         return Ok(());
     }
 
-    pub fn sync_workspace(self: &mut Self) -> Result<(), ScrapError> {
-        return self.app.load_workspace().map(|_| ()).map_err(ScrapError::from_app);
+    /// Loads the workspace from disk. Files that can't be read are skipped and listed in the report with the reason.
+    /// Notes and folders sharing an ID with another one are given a new ID,
+    /// which rewrites their front matter and file name, and are listed in the report.
+    pub fn sync_workspace(self: &mut Self) -> Result<SyncReport, ScrapError> {
+        let AppEvent::WorkspaceLoaded(report) = self.app.load_workspace().map_err(ScrapError::from_app)?;
+
+        return Ok(report);
     }
 
     pub fn list_notes(self: &mut Self) -> Result<Vec<NoteSummary>, ScrapError> {
@@ -31,6 +40,16 @@ impl Scrap {
         return Ok(self.app.list_folders());
     }
 
+    /// Returns the whole workspace as a tree of nested folder nodes, rooted at the workspace itself.
+    pub fn tree(self: &Self) -> Result<FolderNode, ScrapError> {
+        return self.app.tree().map_err(ScrapError::from_app);
+    }
+
+    /// Returns the folder with its direct child notes and folders.
+    pub fn list_children(self: &Self, folder_id: Uuid) -> Result<FolderNode, ScrapError> {
+        return self.app.list_children(folder_id).map_err(ScrapError::from_app);
+    }
+
     pub fn create_note(self: &mut Self, parent_id: Uuid, title: String, file_type: String) -> Result<Uuid, ScrapError> {
         return self
             .app
@@ -45,6 +64,17 @@ impl Scrap {
             .map_err(ScrapError::from_app);
     }
 
+    pub fn rename_folder(self: &mut Self, id: Uuid, new_display_name: String) -> Result<(), ScrapError> {
+        return self
+            .app
+            .rename_folder(id, new_display_name)
+            .map_err(ScrapError::from_app);
+    }
+
+    pub fn move_folder(self: &mut Self, id: Uuid, new_parent_id: Uuid) -> Result<(), ScrapError> {
+        return self.app.move_folder(id, new_parent_id).map_err(ScrapError::from_app);
+    }
+
     pub fn remove_note(self: &mut Self, id: Uuid) -> Result<(), ScrapError> {
         return self.app.remove_note(id).map_err(ScrapError::from_app);
     }
@@ -53,7 +83,150 @@ impl Scrap {
         return self.app.remove_folder(id).map_err(ScrapError::from_app);
     }
 
-    // pub fn get_note(self: &mut Self) -> Result<(), ScrapError> {
-    //     //
-    // }
+    /// Deletes the folder but keeps its notes and subfolders by moving them into the parent folder.
+    pub fn dissolve_folder(self: &mut Self, id: Uuid) -> Result<(), ScrapError> {
+        return self.app.dissolve_folder(id).map_err(ScrapError::from_app);
+    }
+
+    pub fn list_trash(self: &Self) -> Result<Vec<TrashSummary>, ScrapError> {
+        return self.app.list_trash().map_err(ScrapError::from_app);
+    }
+
+    /// Restores a trashed note and returns the ID of the folder it was restored into.
+    pub fn restore_note(self: &mut Self, id: Uuid) -> Result<Uuid, ScrapError> {
+        return self.app.restore_note(id).map_err(ScrapError::from_app);
+    }
+
+    /// Restores a trashed folder with everything inside it and returns the ID of the folder it was restored into.
+    pub fn restore_folder(self: &mut Self, id: Uuid) -> Result<Uuid, ScrapError> {
+        return self.app.restore_folder(id).map_err(ScrapError::from_app);
+    }
+
+    /// Permanently deletes every item in the trash.
+    pub fn empty_trash(self: &mut Self) -> Result<PurgeReport, ScrapError> {
+        return self.app.empty_trash().map_err(ScrapError::from_app);
+    }
+
+    /// Permanently deletes items that have been in the trash for at least `older_than`.
+    pub fn purge_trash(self: &mut Self, older_than: Duration) -> Result<PurgeReport, ScrapError> {
+        return self.app.purge_trash(older_than).map_err(ScrapError::from_app);
+    }
+
+    /// Permanently deletes items older than the workspace's trash retention period, if one is set.
+    pub fn purge_expired_trash(self: &mut Self) -> Result<PurgeReport, ScrapError> {
+        return self.app.purge_expired_trash().map_err(ScrapError::from_app);
+    }
+
+    /// Lets the index hold only note metadata, with bodies read from disk when a note is opened.
+    /// Bodies are still read once while syncing to index them for search and links.
+    pub fn set_lazy_bodies(self: &mut Self, has_lazy_bodies: bool) -> Result<(), ScrapError> {
+        return self.app.set_lazy_bodies(has_lazy_bodies).map_err(ScrapError::from_app);
+    }
+
+    pub fn has_lazy_bodies(self: &Self) -> bool {
+        return self.app.has_lazy_bodies();
+    }
+
+    pub fn get_body_cache_capacity(self: &Self) -> usize {
+        return self.app.get_body_cache_capacity();
+    }
+
+    /// Sets how many recently opened bodies are kept in memory with lazy bodies, 0 disables the cache.
+    pub fn set_body_cache_capacity(self: &mut Self, capacity: usize) {
+        self.app.set_body_cache_capacity(capacity);
+    }
+
+    pub fn get_trash_retention_days(self: &Self) -> Option<u64> {
+        return self.app.get_trash_retention_days();
+    }
+
+    /// Sets how many days trashed items are kept for, `None` keeps them until the trash is emptied.
+    /// Periods too long to be represented as a duration are rejected.
+    pub fn set_trash_retention_days(self: &mut Self, days: Option<u64>) -> Result<(), ScrapError> {
+        return self.app.set_trash_retention_days(days).map_err(ScrapError::from_app);
+    }
+
+    /// Returns the note with its body, which is read from disk if bodies are lazy and it isn't cached.
+    pub fn get_note(self: &mut Self, id: Uuid) -> Result<NoteDetail, ScrapError> {
+        return self.app.get_note(id).map_err(ScrapError::from_app);
+    }
+
+    /// Searches note titles and bodies, returning up to `limit` ranked hits with snippets,
+    /// starting after the first `offset` hits.
+    /// Every word of the query has to match, CJK text is matched by single characters and character bigrams.
+    /// With lazy bodies, the bodies of the returned hits are read from disk for their snippets.
+    pub fn search(self: &mut Self, query: &str, offset: usize, limit: usize) -> Result<Vec<SearchHit>, ScrapError> {
+        return self.app.search(query, offset, limit).map_err(ScrapError::from_app);
+    }
+
+    /// Selects notes with a query such as `type:markdown in:work title:"weekly" -archived created:>2026-01-01`.
+    ///
+    /// Supported fields are `type`, `in` (folder name or ID, including subfolders), `tag` (including nested tags), `title`,
+    /// `created` and `modified` (`YYYY-MM-DD` with an optional `>`, `>=`, `<` or `<=`).
+    /// Bare words and quoted phrases match note text and a leading `-` negates a clause.
+    /// Words with a colon that don't start with a field, like `10:30`, are bare words.
+    pub fn query(self: &Self, query: &str) -> Result<Vec<NoteSummary>, ScrapError> {
+        return self.app.query(query).map_err(ScrapError::from_app);
+    }
+
+    /// Finds notes by title, ignoring case, with prefix and fuzzy matching.
+    /// Matches are ranked best first, an exact title match scores 1.
+    pub fn find_notes_by_title(self: &Self, query: &str) -> Result<Vec<TitleMatch>, ScrapError> {
+        return Ok(self.app.find_notes_by_title(query));
+    }
+
+    pub fn update_note_body(self: &mut Self, id: Uuid, body: String) -> Result<(), ScrapError> {
+        return self.app.update_note_body(id, body).map_err(ScrapError::from_app);
+    }
+
+    pub fn rename_note(self: &mut Self, id: Uuid, new_title: String) -> Result<(), ScrapError> {
+        return self.app.rename_note(id, new_title).map_err(ScrapError::from_app);
+    }
+
+    pub fn move_note(self: &mut Self, id: Uuid, target_folder_id: Uuid) -> Result<(), ScrapError> {
+        return self.app.move_note(id, target_folder_id).map_err(ScrapError::from_app);
+    }
+
+    /// Tags the note, tags nest with `/` like `project/scrap`. The note has to be saved to persist the change.
+    pub fn add_tag(self: &mut Self, id: Uuid, tag: &str) -> Result<(), ScrapError> {
+        return self.app.add_tag(id, tag).map_err(ScrapError::from_app);
+    }
+
+    /// Removes the tag from the note. The note has to be saved to persist the change.
+    pub fn remove_tag(self: &mut Self, id: Uuid, tag: &str) -> Result<(), ScrapError> {
+        return self.app.remove_tag(id, tag).map_err(ScrapError::from_app);
+    }
+
+    /// Lists notes tagged with `tag` or any tag nested below it, so `project` includes `project/scrap`.
+    pub fn list_notes_by_tag(self: &Self, tag: &str) -> Result<Vec<NoteSummary>, ScrapError> {
+        return self.app.list_notes_by_tag(tag).map_err(ScrapError::from_app);
+    }
+
+    pub fn list_tags(self: &Self) -> Result<Vec<String>, ScrapError> {
+        return Ok(self.app.list_tags());
+    }
+
+    /// Returns the note's `[[Title]]` and `[[ID]]` links in the order they appear in its body.
+    /// Broken links are included without a note.
+    pub fn outgoing_links(self: &Self, id: Uuid) -> Result<Vec<NoteLink>, ScrapError> {
+        return self.app.outgoing_links(id).map_err(ScrapError::from_app);
+    }
+
+    /// Returns the notes linking to the note by its title or ID.
+    pub fn backlinks(self: &Self, id: Uuid) -> Result<Vec<NoteSummary>, ScrapError> {
+        return self.app.backlinks(id).map_err(ScrapError::from_app);
+    }
+
+    /// Returns every link in the workspace whose target doesn't match any note.
+    pub fn broken_links(self: &Self) -> Result<Vec<BrokenLink>, ScrapError> {
+        return Ok(self.app.broken_links());
+    }
+
+    pub fn save_note(self: &mut Self, id: Uuid) -> Result<(), ScrapError> {
+        return self.app.save_note(id).map_err(ScrapError::from_app);
+    }
+
+    pub fn save_all(self: &mut Self) -> Result<Vec<Uuid>, ScrapError> {
+        return self.app.save_all().map_err(ScrapError::from_app);
+    }
 }
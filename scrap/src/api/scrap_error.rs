@@ -1,5 +1,6 @@
 use uuid::Uuid;
 
+use crate::api::QueryError;
 use crate::app::AppError;
 
 #[derive(Debug)]
@@ -7,6 +8,14 @@ pub enum ScrapError {
     NoteNotFound(Uuid),
     FolderNotFound(Uuid),
 
+    /// Moving the folder (first) into the target (second) would create a cycle
+    FolderCycle(Uuid, Uuid),
+
+    InvalidQuery(QueryError),
+    InvalidTag(String),
+    /// The trash retention period, in days, is too long to be represented as a duration
+    InvalidRetentionDays(u64),
+
     NotImplemented(String),
     Unknown(String),
 }
@@ -16,6 +25,10 @@ impl ScrapError {
         match err {
             AppError::NoteNotFound(id) => return Self::NoteNotFound(id),
             AppError::FolderNotFound(id) => return Self::FolderNotFound(id),
+            AppError::FolderCycle(id, target_id) => return Self::FolderCycle(id, target_id),
+            AppError::InvalidQuery(err) => return Self::InvalidQuery(err),
+            AppError::InvalidTag(tag) => return Self::InvalidTag(tag),
+            AppError::InvalidRetentionDays(days) => return Self::InvalidRetentionDays(days),
 
             AppError::Workspace(err) => return Self::Unknown(format!("Workspace Error: {:?}", err)),
             AppError::Unknown(msg) => return Self::Unknown(msg),
@@ -0,0 +1,20 @@
+use uuid::Uuid;
+
+pub struct SearchHit {
+    pub id: Uuid,
+    pub title: String,
+    pub score: f32,
+    /// Excerpt of the note body around the first match
+    pub snippet: String,
+}
+
+impl SearchHit {
+    pub fn new(id: Uuid, title: impl Into<String>, score: f32, snippet: impl Into<String>) -> Self {
+        return Self {
+            id,
+            title: title.into(),
+            score,
+            snippet: snippet.into(),
+        };
+    }
+}
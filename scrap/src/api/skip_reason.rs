@@ -0,0 +1,22 @@
+use crate::workspace::WorkspaceError;
+
+#[derive(Debug)]
+pub enum SkipReason {
+    PermissionDenied,
+    /// The file isn't valid UTF-8
+    InvalidEncoding,
+    /// The front matter isn't closed or its ID can't be read
+    MalformedFrontMatter,
+    Unreadable(String),
+}
+
+impl SkipReason {
+    pub fn from_workspace(err: WorkspaceError) -> Self {
+        match err {
+            WorkspaceError::PermissionDenied => return Self::PermissionDenied,
+            WorkspaceError::CorruptedFile => return Self::InvalidEncoding,
+            WorkspaceError::MalformedFrontMatter => return Self::MalformedFrontMatter,
+            err => return Self::Unreadable(format!("{:?}", err)),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use crate::api::SkipReason;
+
+use std::path::PathBuf;
+
+/// A file or directory left out of the workspace while scanning.
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: SkipReason,
+}
+
+impl SkippedFile {
+    pub fn new(path: PathBuf, reason: SkipReason) -> Self {
+        return Self { path, reason };
+    }
+}
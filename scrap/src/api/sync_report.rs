@@ -0,0 +1,13 @@
+use crate::api::{IdConflict, ScanReport};
+
+pub struct SyncReport {
+    pub scan: ScanReport,
+    /// Duplicate IDs found while scanning, in the order they were resolved
+    pub id_conflicts: Vec<IdConflict>,
+}
+
+impl SyncReport {
+    pub fn new(scan: ScanReport, id_conflicts: Vec<IdConflict>) -> Self {
+        return Self { scan, id_conflicts };
+    }
+}
@@ -0,0 +1,13 @@
+use crate::api::NoteSummary;
+
+pub struct TitleMatch {
+    pub note: NoteSummary,
+    /// From 0 to 1, where 1 is an exact match
+    pub score: f32,
+}
+
+impl TitleMatch {
+    pub fn new(note: NoteSummary, score: f32) -> Self {
+        return Self { note, score };
+    }
+}
@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+use uuid::Uuid;
+
+pub struct TrashSummary {
+    pub id: Uuid,
+    pub name: String,
+    pub original_folder_id: Uuid,
+    pub original_path: PathBuf,
+    /// Unix timestamp in seconds
+    pub deleted_at: u64,
+    pub is_folder: bool,
+}
+
+impl TrashSummary {
+    pub fn new(
+        id: Uuid,
+        name: impl Into<String>,
+        original_folder_id: Uuid,
+        original_path: impl Into<PathBuf>,
+        deleted_at: u64,
+        is_folder: bool,
+    ) -> Self {
+        return Self {
+            id,
+            name: name.into(),
+            original_folder_id,
+            original_path: original_path.into(),
+            deleted_at,
+            is_folder,
+        };
+    }
+}
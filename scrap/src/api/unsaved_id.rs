@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+/// A note or folder found without an ID whose generated ID couldn't be written to disk.
+/// It's loaded anyway, but gets a different ID on every sync until the ID can be written.
+pub struct UnsavedId {
+    pub path: PathBuf,
+    pub is_folder: bool,
+    /// Why writing the ID failed
+    pub error: String,
+}
+
+impl UnsavedId {
+    pub fn new(path: PathBuf, is_folder: bool, error: String) -> Self {
+        return Self { path, is_folder, error };
+    }
+}
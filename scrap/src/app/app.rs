@@ -1,19 +1,32 @@
-use crate::api::{FolderSummary, NoteSummary};
+use crate::api::{
+    BrokenLink, FolderNode, FolderSummary, IdConflict, NoteDetail, NoteLink, NoteSummary, PurgeReport, SearchHit,
+    SyncReport, TitleMatch, TrashSummary,
+};
 use crate::app::{AppError, AppEvent};
 use crate::index::{Index, IndexError};
-use crate::model::{Folder, Note};
+use crate::model::{Folder, Note, TrashEntry};
+use crate::parser::parse_query::parse_query;
+use crate::text::normalize_tag::normalize_tag;
+use crate::text::property_value::property_value;
+use crate::text::snippet::snippet;
 use crate::workspace::{Workspace, WorkspaceError};
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::result;
+use std::time::Duration;
 use uuid::Uuid;
 use uuid::uuid;
 
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
 pub struct App {
     workspace: Workspace,
     workspace_id: Uuid,
     index: Index,
+    /// When set, the index only holds note metadata and bodies are read from disk on demand
+    has_lazy_bodies: bool,
 }
 
 impl App {
@@ -22,6 +35,7 @@ impl App {
             workspace: Workspace::new(),
             workspace_id: uuid!("3e206920-6c75-7620-7520-6d722063656f"),
             index: Index::new(),
+            has_lazy_bodies: false,
         };
     }
 
@@ -34,17 +48,24 @@ impl App {
     }
 
     pub fn load_workspace(self: &mut Self) -> Result<AppEvent, AppError> {
-        let (loaded_notes, loaded_folders) = self
+        let (mut loaded_notes, mut loaded_folders, scan_report, cached_ids) = self
             .workspace
-            .scan_workspace(self.workspace_id.clone())
+            .scan_workspace(self.workspace_id.clone(), self.has_lazy_bodies)
             .map_err(|err| AppError::Unknown(format!("Failed to load workspace with error: {:?}", err)))?;
 
-        // TODO: Map and handle index errors to app errors
-        let notes_report = self.index.extend_notes(loaded_notes);
-        let folders_report = self.index.extend_folders(loaded_folders);
-        // TODO: Handle conflict reports
+        let id_conflicts = self.resolve_duplicate_ids(&mut loaded_notes, &mut loaded_folders, &cached_ids);
+
+        // Folders go first so notes can register with their parents,
+        // IDs are unique by now so any conflict left is an item indexed by an earlier sync
+        self.index
+            .extend_folders(loaded_folders)
+            .map_err(AppError::from_index)?;
+        self.index.extend_notes(loaded_notes).map_err(AppError::from_index)?;
 
-        return Ok(AppEvent::WorkspaceLoaded);
+        // Files may have changed since their bodies were cached
+        self.index.clear_body_cache();
+
+        return Ok(AppEvent::WorkspaceLoaded(SyncReport::new(scan_report, id_conflicts)));
     }
 
     pub fn list_notes(self: &Self) -> Vec<NoteSummary> {
@@ -55,10 +76,26 @@ impl App {
         return self.index.list_folders().unwrap_or_default();
     }
 
+    /// Returns the whole workspace as a tree, starting from the workspace root.
+    pub fn tree(self: &Self) -> Result<FolderNode, AppError> {
+        return self
+            .index
+            .get_folder_node(self.workspace_id, self.workspace_id, None)
+            .map_err(AppError::from_index);
+    }
+
+    /// Returns the folder with its direct child notes and folders, child folders are not expanded.
+    pub fn list_children(self: &Self, folder_id: Uuid) -> Result<FolderNode, AppError> {
+        return self
+            .index
+            .get_folder_node(folder_id, self.workspace_id, Some(1))
+            .map_err(AppError::from_index);
+    }
+
     pub fn create_note(self: &mut Self, parent_id: Uuid, title: String, file_type: String) -> Result<Uuid, AppError> {
         let parent_dir = self.get_directory(parent_id)?;
 
-        match self.workspace.create_note(parent_dir, &title, &file_type) {
+        match self.workspace.create_note(parent_dir, &title, &file_type, parent_id) {
             Ok(note) => {
                 let note_id = note.get_id();
 
@@ -75,24 +112,370 @@ impl App {
         }
     }
 
+    /// Moves the note into the trash.
+    /// The index is only updated once the note has been moved successfully.
     pub fn remove_note(self: &mut Self, id: Uuid) -> Result<(), AppError> {
-        // Update index
-        let mut note_to_delete = self.index.remove_note(id).map_err(AppError::from_index)?;
+        let note_to_delete = self.index.get_note_mut(id).map_err(AppError::from_index)?;
 
         // Move note to trash
         self.workspace
-            .move_note_to_trash(&mut note_to_delete)
+            .move_note_to_trash(note_to_delete)
             .map_err(|err| AppError::Unknown(format!("Workpace error: {:?}", err)))?;
 
+        // Update index
+        self.index.remove_note(id).map_err(AppError::from_index)?;
+
+        return Ok(());
+    }
+
+    pub fn list_trash(self: &Self) -> Result<Vec<TrashSummary>, AppError> {
+        let entries = self.workspace.list_trash().map_err(AppError::Workspace)?;
+
+        return Ok(entries.iter().map(Self::to_trash_summary).collect());
+    }
+
+    /// Permanently deletes every item in the trash, including files never recorded in the trash manifest.
+    pub fn empty_trash(self: &mut Self) -> Result<PurgeReport, AppError> {
+        let (deleted, failed) = self.workspace.purge_trash(None).map_err(AppError::Workspace)?;
+        let untracked_paths = self.workspace.purge_untracked_trash().map_err(AppError::Workspace)?;
+
+        return Ok(PurgeReport::new(
+            deleted.iter().map(Self::to_trash_summary).collect(),
+            failed.iter().map(|e| e.get_id()).collect(),
+            untracked_paths,
+        ));
+    }
+
+    /// Permanently deletes items that have been in the trash for at least `older_than`.
+    pub fn purge_trash(self: &mut Self, older_than: Duration) -> Result<PurgeReport, AppError> {
+        let (deleted, failed) = self
+            .workspace
+            .purge_trash(Some(older_than))
+            .map_err(AppError::Workspace)?;
+
+        return Ok(PurgeReport::new(
+            deleted.iter().map(Self::to_trash_summary).collect(),
+            failed.iter().map(|e| e.get_id()).collect(),
+            Vec::new(),
+        ));
+    }
+
+    /// Purges trashed items older than the workspace's retention period.
+    /// Nothing is deleted when no retention period is configured.
+    pub fn purge_expired_trash(self: &mut Self) -> Result<PurgeReport, AppError> {
+        let Some(days) = self.workspace.get_settings().trash_retention_days else {
+            return Ok(PurgeReport::new(Vec::new(), Vec::new(), Vec::new()));
+        };
+
+        // The settings file can be edited by hand, so the period is checked here as well as when it's set
+        let retention_period = Self::retention_period(days).ok_or(AppError::InvalidRetentionDays(days))?;
+
+        return self.purge_trash(retention_period);
+    }
+
+    pub fn get_trash_retention_days(self: &Self) -> Option<u64> {
+        return self.workspace.get_settings().trash_retention_days;
+    }
+
+    pub fn set_trash_retention_days(self: &mut Self, days: Option<u64>) -> Result<(), AppError> {
+        if let Some(days) = days
+            && Self::retention_period(days).is_none()
+        {
+            return Err(AppError::InvalidRetentionDays(days));
+        }
+
+        let mut settings = self.workspace.get_settings().clone();
+        settings.trash_retention_days = days;
+
+        return self.workspace.save_settings(settings).map_err(AppError::Workspace);
+    }
+
+    /// Restores a trashed note into its original folder, or into the workspace root
+    /// if that folder no longer exists. Returns the ID of the folder it was restored into.
+    pub fn restore_note(self: &mut Self, id: Uuid) -> Result<Uuid, AppError> {
+        let entries = self.workspace.list_trash().map_err(AppError::Workspace)?;
+
+        let entry = entries
+            .iter()
+            .find(|e| e.get_id() == id && !e.is_folder())
+            .ok_or(AppError::NoteNotFound(id))?;
+
+        let parent_id = match self.get_directory(entry.get_parent_id()) {
+            Ok(_) => entry.get_parent_id(),
+            Err(_) => self.workspace_id,
+        };
+        let parent_dir = self.get_directory(parent_id)?;
+
+        let note = self
+            .workspace
+            .restore_note(id, parent_dir, parent_id)
+            .map_err(AppError::Workspace)?;
+
+        self.index.insert_note(note).map_err(AppError::from_index)?;
+
+        if self.has_lazy_bodies {
+            self.index.unload_body(id).map_err(AppError::from_index)?;
+        }
+
+        return Ok(parent_id);
+    }
+
+    /// Restores a trashed folder, with everything inside it, into its original parent folder,
+    /// or into the workspace root if that folder no longer exists.
+    /// Returns the ID of the folder it was restored into.
+    pub fn restore_folder(self: &mut Self, id: Uuid) -> Result<Uuid, AppError> {
+        let entries = self.workspace.list_trash().map_err(AppError::Workspace)?;
+
+        let entry = entries
+            .iter()
+            .find(|e| e.get_id() == id && e.is_folder())
+            .ok_or(AppError::FolderNotFound(id))?;
+
+        let parent_id = match self.get_directory(entry.get_parent_id()) {
+            Ok(_) => entry.get_parent_id(),
+            Err(_) => self.workspace_id,
+        };
+        let parent_dir = self.get_directory(parent_id)?;
+
+        let (notes, folders) = self
+            .workspace
+            .restore_folder(id, parent_dir, parent_id, self.has_lazy_bodies)
+            .map_err(AppError::Workspace)?;
+
+        // Folders go first so notes can be attached to their parent folders
+        self.index.extend_folders(folders).map_err(AppError::from_index)?;
+        self.index.extend_notes(notes).map_err(AppError::from_index)?;
+
+        return Ok(parent_id);
+    }
+
+    /// Returns `limit` search hits, best first, after skipping the first `offset` ones.
+    /// Only the bodies of the returned hits are read for their snippets.
+    pub fn search(self: &mut Self, query: &str, offset: usize, limit: usize) -> Result<Vec<SearchHit>, AppError> {
+        let mut hits = Vec::new();
+
+        for (id, score) in self.index.search(query).into_iter().skip(offset).take(limit) {
+            // A body that can't be read, like one deleted since the last sync, only leaves the snippet empty
+            let body = self.read_note_body(id).unwrap_or_default();
+            let note = self.index.get_note(id).map_err(AppError::from_index)?;
+
+            hits.push(SearchHit::new(id, note.get_title(), score, snippet(&body, query)));
+        }
+
+        return Ok(hits);
+    }
+
+    pub fn query(self: &Self, query: &str) -> Result<Vec<NoteSummary>, AppError> {
+        let query = parse_query(query).map_err(AppError::InvalidQuery)?;
+
+        return Ok(self.index.query(&query, self.workspace_id));
+    }
+
+    pub fn find_notes_by_title(self: &Self, query: &str) -> Vec<TitleMatch> {
+        return self.index.find_notes_by_title(query);
+    }
+
+    pub fn get_note(self: &mut Self, id: Uuid) -> Result<NoteDetail, AppError> {
+        let body = self.read_note_body(id)?;
+        let note = self.index.get_note(id).map_err(AppError::from_index)?;
+
+        return Ok(NoteDetail::new(
+            note.get_id(),
+            note.get_title(),
+            note.get_file_type(),
+            note.get_parent_id(),
+            note.get_relative_path(),
+            note.get_tags().to_vec(),
+            note.get_properties()
+                .iter()
+                .map(|(key, value)| (key.clone(), property_value(value)))
+                .collect(),
+            body,
+        ));
+    }
+
+    /// Returns the note's body from memory, the body cache or, if it was unloaded, from disk.
+    fn read_note_body(self: &mut Self, id: Uuid) -> Result<String, AppError> {
+        if let Some(body) = self.index.get_loaded_body(id).map_err(AppError::from_index)? {
+            return Ok(body);
+        }
+
+        let note = self.index.get_note(id).map_err(AppError::from_index)?;
+
+        let loaded_note = self
+            .workspace
+            .load_note(note.get_relative_path(), note.get_parent_id())
+            .map_err(AppError::Workspace)?;
+        let body = loaded_note.get_body().unwrap_or_default().to_string();
+
+        self.index.cache_body(id, body.clone());
+
+        return Ok(body);
+    }
+
+    /// Switches between holding every body in memory and holding only note metadata.
+    /// Turning lazy bodies off reads every unloaded body back from disk.
+    pub fn set_lazy_bodies(self: &mut Self, has_lazy_bodies: bool) -> Result<(), AppError> {
+        self.has_lazy_bodies = has_lazy_bodies;
+
+        if has_lazy_bodies {
+            self.index.unload_bodies();
+            return Ok(());
+        }
+
+        for id in self.index.get_unloaded_note_ids() {
+            let body = self.read_note_body(id)?;
+            self.index.load_body(id, body).map_err(AppError::from_index)?;
+        }
+
+        self.index.clear_body_cache();
+
+        return Ok(());
+    }
+
+    pub fn has_lazy_bodies(self: &Self) -> bool {
+        return self.has_lazy_bodies;
+    }
+
+    pub fn get_body_cache_capacity(self: &Self) -> usize {
+        return self.index.get_body_cache_capacity();
+    }
+
+    /// Sets how many unloaded bodies are kept in memory after being read, 0 disables the cache.
+    pub fn set_body_cache_capacity(self: &mut Self, capacity: usize) {
+        self.index.set_body_cache_capacity(capacity);
+    }
+
+    /// Replaces the note's body in memory and marks it as dirty.
+    /// Changes are not written to disk until the note is saved.
+    pub fn update_note_body(self: &mut Self, id: Uuid, body: String) -> Result<(), AppError> {
+        return self.index.update_note_body(id, &body).map_err(AppError::from_index);
+    }
+
+    /// Tags the note in memory and marks it as dirty, tags nest with `/` like `project/scrap`.
+    /// Changes are not written to disk until the note is saved.
+    pub fn add_tag(self: &mut Self, id: Uuid, tag: &str) -> Result<(), AppError> {
+        let normalized_tag = normalize_tag(tag).ok_or(AppError::InvalidTag(tag.to_string()))?;
+
+        self.index.add_tag(id, normalized_tag).map_err(AppError::from_index)?;
+
+        return Ok(());
+    }
+
+    /// Removes the tag from the note in memory and marks it as dirty.
+    /// Changes are not written to disk until the note is saved.
+    pub fn remove_tag(self: &mut Self, id: Uuid, tag: &str) -> Result<(), AppError> {
+        // Tags that can't be normalized are kept from front matter as written, so they're removed as written
+        let normalized_tag = normalize_tag(tag).unwrap_or(tag.trim().to_string());
+
+        self.index
+            .remove_tag(id, &normalized_tag)
+            .map_err(AppError::from_index)?;
+
+        return Ok(());
+    }
+
+    pub fn list_notes_by_tag(self: &Self, tag: &str) -> Result<Vec<NoteSummary>, AppError> {
+        let normalized_tag = normalize_tag(tag).ok_or(AppError::InvalidTag(tag.to_string()))?;
+
+        return Ok(self.index.get_notes_by_tag(&normalized_tag));
+    }
+
+    pub fn list_tags(self: &Self) -> Vec<String> {
+        return self.index.get_tags();
+    }
+
+    pub fn outgoing_links(self: &Self, id: Uuid) -> Result<Vec<NoteLink>, AppError> {
+        return self.index.get_outgoing_links(id).map_err(AppError::from_index);
+    }
+
+    pub fn backlinks(self: &Self, id: Uuid) -> Result<Vec<NoteSummary>, AppError> {
+        return self.index.get_backlinks(id).map_err(AppError::from_index);
+    }
+
+    pub fn broken_links(self: &Self) -> Vec<BrokenLink> {
+        return self.index.get_broken_links();
+    }
+
+    /// Writes the note to disk if it has unsaved changes.
+    pub fn save_note(self: &mut Self, id: Uuid) -> Result<(), AppError> {
+        let note = self.index.get_note_mut(id).map_err(AppError::from_index)?;
+
+        if !note.is_dirty() {
+            return Ok(());
+        }
+
+        self.workspace.save_note(note).map_err(AppError::Workspace)?;
+        note.mark_as_saved();
+
+        if self.has_lazy_bodies {
+            self.index.unload_body(id).map_err(AppError::from_index)?;
+        }
+
         return Ok(());
     }
 
-    pub fn get_note(self: &Self, id: Uuid) -> Result<String, AppError> {
-        return self.index.get_note_body(id).map_err(AppError::from_index);
+    /// Writes every note with unsaved changes to disk and returns the saved IDs.
+    pub fn save_all(self: &mut Self) -> Result<Vec<Uuid>, AppError> {
+        let dirty_ids = self.index.get_dirty_note_ids();
+
+        for id in &dirty_ids {
+            self.save_note(*id)?;
+        }
+
+        return Ok(dirty_ids);
+    }
+
+    /// Renames the note, its file and its front matter in one operation.
+    /// The index is only updated once the workspace has been renamed successfully.
+    pub fn rename_note(self: &mut Self, id: Uuid, new_title: String) -> Result<(), AppError> {
+        // The note is indexed for search again, which needs its body
+        let is_body_unloaded = !self.index.get_note(id).map_err(AppError::from_index)?.is_body_loaded();
+        if is_body_unloaded {
+            let body = self.read_note_body(id)?;
+            self.index.load_body(id, body).map_err(AppError::from_index)?;
+        }
+
+        let note = self.index.get_note(id).map_err(AppError::from_index)?;
+
+        let new_path = self
+            .workspace
+            .rename_note(note, &new_title)
+            .map_err(AppError::Workspace)?;
+
+        self.index
+            .rename_note(id, &new_title, new_path)
+            .map_err(AppError::from_index)?;
+
+        if is_body_unloaded {
+            self.index.unload_body(id).map_err(AppError::from_index)?;
+        }
+
+        return Ok(());
     }
 
-    // pub fn save_note(self: &Self, id: Uuid) {}
-    // pub fn delete_note(self: &Self, id: Uuid) {}
+    /// Moves the note into the target folder on disk and in the index.
+    pub fn move_note(self: &mut Self, id: Uuid, target_folder_id: Uuid) -> Result<(), AppError> {
+        let note = self.index.get_note(id).map_err(AppError::from_index)?;
+
+        // Nothing to do when the note is already inside the target folder
+        if note.get_parent_id() == target_folder_id {
+            return Ok(());
+        }
+
+        let target_dir = self.get_directory(target_folder_id)?;
+
+        let new_path = self
+            .workspace
+            .move_note(note, target_dir)
+            .map_err(AppError::Workspace)?;
+
+        self.index
+            .move_note(id, target_folder_id, new_path)
+            .map_err(AppError::from_index)?;
+
+        return Ok(());
+    }
 
     pub fn create_folder(self: &mut Self, parent_id: Uuid, display_name: String) -> Result<Uuid, AppError> {
         let parent_dir = self.get_directory(parent_id)?;
@@ -114,19 +497,337 @@ impl App {
         }
     }
 
-    //
+    /// Renames the folder, its directory and its metadata file in one operation.
+    /// The index is only updated once the workspace has been renamed successfully.
+    pub fn rename_folder(self: &mut Self, id: Uuid, new_display_name: String) -> Result<(), AppError> {
+        self.save_folder_ids(id)?;
+        let folder = self.index.get_folder(id).map_err(AppError::from_index)?;
+
+        let new_dir = self
+            .workspace
+            .rename_folder(folder, &new_display_name)
+            .map_err(AppError::Workspace)?;
+
+        self.index
+            .rename_folder(id, &new_display_name, new_dir)
+            .map_err(AppError::from_index)?;
+
+        return Ok(());
+    }
+
+    /// Moves the folder and its whole subtree under the new parent folder.
+    /// Moving a folder into itself or one of its descendants is rejected.
+    pub fn move_folder(self: &mut Self, id: Uuid, new_parent_id: Uuid) -> Result<(), AppError> {
+        let folder = self.index.get_folder(id).map_err(AppError::from_index)?;
+
+        // Nothing to do when the folder is already inside the new parent
+        if folder.get_parent_id() == new_parent_id {
+            return Ok(());
+        }
+
+        if self.index.is_folder_within(new_parent_id, id) {
+            return Err(AppError::FolderCycle(id, new_parent_id));
+        }
+
+        self.save_folder_ids(id)?;
+        let folder = self.index.get_folder(id).map_err(AppError::from_index)?;
+        let target_dir = self.get_directory(new_parent_id)?;
+
+        let new_dir = self
+            .workspace
+            .move_folder(folder, target_dir)
+            .map_err(AppError::Workspace)?;
+
+        self.index
+            .move_folder(id, new_parent_id, new_dir)
+            .map_err(AppError::from_index)?;
+
+        return Ok(());
+    }
+
+    /// Moves the folder, along with all the notes and folders inside, into the trash.
+    /// The index is only updated once the folder has been moved successfully.
     pub fn remove_folder(self: &mut Self, id: Uuid) -> Result<(), AppError> {
-        // Update index
-        let folder_to_delete = self.index.remove_folder(id).map_err(AppError::from_index)?;
+        // Folders are read back from the trash without the cache
+        self.save_folder_ids(id)?;
+        let folder_to_delete = self.index.get_folder(id).map_err(AppError::from_index)?;
 
-        // Remove folder directory from workspace along with all the notes inside
+        // Move folder directory to trash along with all the notes inside
         self.workspace
-            .delete_folder(&folder_to_delete)
+            .move_folder_to_trash(folder_to_delete)
             .map_err(|err| AppError::Unknown(format!("Workpace error: {:?}", err)))?;
 
+        // Update index
+        self.index.remove_folder(id).map_err(AppError::from_index)?;
+
         return Ok(());
     }
 
+    /// Deletes the folder but keeps its content by moving its child notes and folders into the parent folder.
+    /// If moving a child fails, the children moved so far stay in the parent and the folder is kept.
+    pub fn dissolve_folder(self: &mut Self, id: Uuid) -> Result<(), AppError> {
+        let folder = self.index.get_folder(id).map_err(AppError::from_index)?;
+
+        let parent_id = folder.get_parent_id();
+        let child_notes = folder.get_child_notes().clone();
+        let child_folders = folder.get_child_folders().clone();
+
+        for child_note in child_notes {
+            self.move_note(child_note, parent_id)?;
+        }
+
+        for child_folder in child_folders {
+            self.move_folder(child_folder, parent_id)?;
+        }
+
+        // Delete the now empty folder directory
+        let folder_to_delete = self.index.get_folder(id).map_err(AppError::from_index)?;
+        self.workspace
+            .delete_empty_folder(folder_to_delete)
+            .map_err(AppError::Workspace)?;
+
+        // Update index
+        self.index.remove_folder(id).map_err(AppError::from_index)?;
+
+        return Ok(());
+    }
+
+    /// Writes the metadata file of the folder and of every folder inside it that doesn't have one yet,
+    /// before their directories move away from the paths the index cache knows their IDs by.
+    fn save_folder_ids(self: &Self, id: Uuid) -> Result<(), AppError> {
+        let mut pending = vec![id];
+
+        while let Some(id) = pending.pop() {
+            let folder = self.index.get_folder(id).map_err(AppError::from_index)?;
+
+            self.workspace
+                .ensure_folder_metadata(folder)
+                .map_err(AppError::Workspace)?;
+
+            pending.extend(folder.get_child_folders());
+        }
+
+        return Ok(());
+    }
+
+    /// Gives scanned notes and folders that share an ID with another one a new ID, rewriting them on disk.
+    /// The ID stays with the item the index or the index cache already had at its path,
+    /// then with the one whose file name holds the ID, and only then with the one with the shortest path.
+    /// A duplicate that can't be rewritten is left out, along with everything inside it.
+    fn resolve_duplicate_ids(
+        self: &Self,
+        notes: &mut Vec<Note>,
+        folders: &mut Vec<Folder>,
+        cached_ids: &HashMap<PathBuf, Uuid>,
+    ) -> Vec<IdConflict> {
+        let mut conflicts = Vec::new();
+        let mut skipped_paths: Vec<PathBuf> = Vec::new();
+
+        // Parent folders come first, so a renamed directory's contents are moved along before they're checked
+        let mut folder_order: Vec<usize> = (0..folders.len()).collect();
+        folder_order.sort_by(|&a, &b| {
+            Self::compare_scanned_paths(folders[a].get_relative_path(), folders[b].get_relative_path())
+        });
+
+        // Keepers are picked before any directory is renamed, while paths still match the cache
+        let mut folder_keepers: HashMap<Uuid, usize> = HashMap::new();
+        for i in 0..folders.len() {
+            let id = folders[i].get_id();
+            let path = folders[i].get_relative_path();
+
+            let is_better = folder_keepers.get(&id).is_none_or(|&kept| {
+                let kept_path = folders[kept].get_relative_path();
+                self.compare_keepers(id, true, path, kept_path, cached_ids) == Ordering::Less
+            });
+            if is_better {
+                folder_keepers.insert(id, i);
+            }
+        }
+
+        let mut note_keepers: HashMap<Uuid, usize> = HashMap::new();
+        for i in 0..notes.len() {
+            let id = notes[i].get_id();
+            let path = notes[i].get_relative_path();
+
+            let is_better = note_keepers.get(&id).is_none_or(|&kept| {
+                let kept_path = notes[kept].get_relative_path();
+                self.compare_keepers(id, false, path, kept_path, cached_ids) == Ordering::Less
+            });
+            if is_better {
+                note_keepers.insert(id, i);
+            }
+        }
+
+        for i in folder_order {
+            let id = folders[i].get_id();
+            let current_dir = folders[i].get_relative_path().to_path_buf();
+
+            if skipped_paths.iter().any(|path| current_dir.starts_with(path)) {
+                continue;
+            }
+
+            let kept = folder_keepers[&id];
+            if kept == i {
+                continue;
+            }
+            let kept_path = folders[kept].get_relative_path().to_path_buf();
+
+            let new_id = Uuid::new_v4();
+            let new_dir = match self.workspace.reassign_folder_id(&folders[i], new_id) {
+                Ok(new_dir) => new_dir,
+                Err(_) => {
+                    conflicts.push(IdConflict::new(id, true, kept_path, current_dir.clone(), None));
+                    skipped_paths.push(current_dir);
+                    continue;
+                }
+            };
+
+            folders[i].set_id(new_id);
+            folders[i].set_relative_path(new_dir.clone());
+
+            // Follow the directory rename and move direct children over to the new ID
+            for folder in folders.iter_mut() {
+                if let Some(path) = Self::rebase_path(folder.get_relative_path(), &current_dir, &new_dir) {
+                    folder.set_relative_path(path);
+
+                    if folder.get_parent_id() == id && folder.get_relative_path().parent() == Some(new_dir.as_path()) {
+                        folder.set_parent_id(new_id);
+                    }
+                }
+            }
+            for note in notes.iter_mut() {
+                if let Some(path) = Self::rebase_path(note.get_relative_path(), &current_dir, &new_dir) {
+                    note.set_relative_path(path);
+
+                    if note.get_parent_id() == id && note.get_relative_path().parent() == Some(new_dir.as_path()) {
+                        note.set_parent_id(new_id);
+                    }
+                }
+            }
+
+            conflicts.push(IdConflict::new(id, true, kept_path, new_dir, Some(new_id)));
+        }
+
+        let mut note_order: Vec<usize> = (0..notes.len()).collect();
+        note_order
+            .sort_by(|&a, &b| Self::compare_scanned_paths(notes[a].get_relative_path(), notes[b].get_relative_path()));
+
+        for i in note_order {
+            let id = notes[i].get_id();
+            let current_path = notes[i].get_relative_path().to_path_buf();
+
+            if skipped_paths.iter().any(|path| current_path.starts_with(path)) {
+                continue;
+            }
+
+            let kept = note_keepers[&id];
+            if kept == i {
+                continue;
+            }
+            let kept_path = notes[kept].get_relative_path().to_path_buf();
+
+            let new_id = Uuid::new_v4();
+            match self.workspace.reassign_note_id(&notes[i], new_id) {
+                Ok(new_path) => {
+                    notes[i].set_id(new_id);
+                    notes[i].set_relative_path(new_path.clone());
+
+                    conflicts.push(IdConflict::new(id, false, kept_path, new_path, Some(new_id)));
+                }
+                Err(_) => {
+                    conflicts.push(IdConflict::new(id, false, kept_path, current_path.clone(), None));
+                    skipped_paths.push(current_path);
+                }
+            }
+        }
+
+        if !conflicts.is_empty() {
+            notes.retain(|note| {
+                !skipped_paths
+                    .iter()
+                    .any(|path| note.get_relative_path().starts_with(path))
+            });
+            folders.retain(|folder| {
+                !skipped_paths
+                    .iter()
+                    .any(|path| folder.get_relative_path().starts_with(path))
+            });
+
+            // The scanned child lists still hold old IDs, the index registers children again as they're inserted
+            for folder in folders.iter_mut() {
+                folder.clear_children();
+            }
+        }
+
+        return conflicts;
+    }
+
+    /// Orders two scanned items with the same ID by which one should keep it, the first one keeps it.
+    fn compare_keepers(
+        self: &Self,
+        id: Uuid,
+        is_folder: bool,
+        a: &Path,
+        b: &Path,
+        cached_ids: &HashMap<PathBuf, Uuid>,
+    ) -> Ordering {
+        let is_known = |path: &Path| {
+            let is_indexed = match is_folder {
+                true => self.index.get_folder(id).is_ok_and(|f| f.get_relative_path() == path),
+                false => self.index.get_note(id).is_ok_and(|n| n.get_relative_path() == path),
+            };
+
+            return is_indexed || cached_ids.get(path) == Some(&id);
+        };
+
+        // Names created by the app end with the ID, a copy's name usually adds to it
+        let id_suffix = format!("____{}", id);
+        let has_id_in_name = |path: &Path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().contains(&id_suffix))
+        };
+
+        return is_known(b)
+            .cmp(&is_known(a))
+            .then(has_id_in_name(b).cmp(&has_id_in_name(a)))
+            .then(Self::compare_scanned_paths(a, b));
+    }
+
+    /// Orders paths by depth, then length, then name.
+    fn compare_scanned_paths(a: &Path, b: &Path) -> Ordering {
+        return a
+            .components()
+            .count()
+            .cmp(&b.components().count())
+            .then(a.as_os_str().len().cmp(&b.as_os_str().len()))
+            .then(a.cmp(b));
+    }
+
+    /// Returns the path moved from `old_dir` into `new_dir`, if it was inside `old_dir`.
+    fn rebase_path(path: &Path, old_dir: &Path, new_dir: &Path) -> Option<PathBuf> {
+        if path == old_dir {
+            return None;
+        }
+
+        return path.strip_prefix(old_dir).ok().map(|rest| new_dir.join(rest));
+    }
+
+    /// Returns the retention period as a duration, `None` if it's too long to be represented.
+    fn retention_period(days: u64) -> Option<Duration> {
+        return days.checked_mul(SECONDS_PER_DAY).map(Duration::from_secs);
+    }
+
+    fn to_trash_summary(entry: &TrashEntry) -> TrashSummary {
+        return TrashSummary::new(
+            entry.get_id(),
+            entry.get_name(),
+            entry.get_parent_id(),
+            entry.get_original_path(),
+            entry.get_deleted_at(),
+            entry.is_folder(),
+        );
+    }
+
     fn get_directory(self: &Self, id: Uuid) -> Result<&Path, AppError> {
         if id == self.workspace_id {
             return Ok(Path::new(""));
@@ -1,5 +1,6 @@
 use uuid::Uuid;
 
+use crate::api::QueryError;
 use crate::index::IndexError;
 use crate::workspace::WorkspaceError;
 
@@ -8,6 +9,14 @@ pub enum AppError {
     NoteNotFound(Uuid),
     FolderNotFound(Uuid),
 
+    /// Moving the folder (first) into the target (second) would create a cycle
+    FolderCycle(Uuid, Uuid),
+
+    InvalidQuery(QueryError),
+    InvalidTag(String),
+    /// The trash retention period, in days, is too long to be represented as a duration
+    InvalidRetentionDays(u64),
+
     Workspace(WorkspaceError),
     Unknown(String),
 }
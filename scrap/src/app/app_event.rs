@@ -1,7 +1,7 @@
-use crate::api::{FolderSummary, NoteSummary};
+use crate::api::{FolderSummary, NoteSummary, SyncReport};
 
 use uuid::Uuid;
 
 pub enum AppEvent {
-    WorkspaceLoaded,
+    WorkspaceLoaded(SyncReport),
 }
@@ -6,11 +6,14 @@ use std::io;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Opens the file for reading only, so read-only files can be read as well.
+/// Writing goes through `write_file`.
 pub fn open_file(workspace_dir: &Path, target_dir: &Path) -> io::Result<File> {
     let target = resolve_existing_path(workspace_dir, target_dir)?;
 
-    return OpenOptions::new().read(true).write(true).create(false).open(&target);
+    return OpenOptions::new().read(true).open(&target);
 }
 
 pub fn create_file(workspace_dir: &Path, target_dir: &Path) -> io::Result<File> {
@@ -64,6 +67,21 @@ pub fn delete_dir(workspace_dir: &Path, target_dir: &Path) -> io::Result<()> {
     return fs::remove_dir(&target);
 }
 
+pub fn delete_dir_all(workspace_dir: &Path, target_dir: &Path) -> io::Result<()> {
+    let target = resolve_existing_dir(workspace_dir, target_dir)?;
+
+    return fs::remove_dir_all(&target);
+}
+
+pub fn move_dir(workspace_dir: &Path, current_dir: &Path, new_dir: &Path) -> io::Result<()> {
+    let from = resolve_existing_dir(workspace_dir, current_dir)?;
+    let to = resolve_new_dir(workspace_dir, new_dir)?;
+
+    fs::rename(from, to)?;
+
+    return Ok(());
+}
+
 pub fn read_directory(workspace_dir: &Path, target_dir: &Path) -> io::Result<ReadDir> {
     let target_dir = resolve_existing_dir(workspace_dir, target_dir)?;
 
@@ -78,6 +96,42 @@ pub fn ensure_dir(workspace_dir: &Path, target_dir: &Path) -> io::Result<()> {
     return Ok(());
 }
 
+/// Returns the creation and last modification times of the file as Unix timestamps in seconds.
+/// Falls back to the modification time on file systems that don't record creation times.
+pub fn file_times(workspace_dir: &Path, target_dir: &Path) -> io::Result<(u64, u64)> {
+    let target = resolve_existing_path(workspace_dir, target_dir)?;
+
+    let metadata = fs::metadata(&target)?;
+
+    let modified = metadata.modified()?;
+    let created = metadata.created().unwrap_or(modified);
+
+    let to_timestamp = |time: SystemTime| {
+        return time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+    };
+
+    return Ok((to_timestamp(created), to_timestamp(modified)));
+}
+
+/// Returns the file's last modification time in nanoseconds since the Unix epoch and its size in bytes.
+pub fn file_stat(workspace_dir: &Path, target_dir: &Path) -> io::Result<(u64, u64)> {
+    let target = resolve_existing_path(workspace_dir, target_dir)?;
+
+    let metadata = fs::metadata(&target)?;
+
+    let modified = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+
+    return Ok((modified, metadata.len()));
+}
+
+pub fn path_exists(workspace_dir: &Path, target_dir: &Path) -> bool {
+    return workspace_dir.join(target_dir).exists();
+}
+
 fn resolve_new_path(workspace_dir: &Path, target_dir: &Path) -> io::Result<PathBuf> {
     let target = workspace_dir.join(target_dir);
 
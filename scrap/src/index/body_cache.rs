@@ -0,0 +1,163 @@
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+/// Least recently used cache for bodies of notes that don't hold their body in memory.
+///
+/// Every use of a body gets a new generation, and `usage` records each use in order.
+/// Records older than the body's current generation are stale and skipped when evicting,
+/// so marking a body as used never has to search `usage`.
+pub struct BodyCache {
+    /// Maximum number of cached bodies, 0 disables the cache
+    capacity: usize,
+    /// Cached bodies with the generation of their last use
+    bodies: HashMap<Uuid, (String, u64)>,
+    /// Uses of cached IDs from least to most recent, including stale ones
+    usage: VecDeque<(Uuid, u64)>,
+    next_generation: u64,
+}
+
+impl BodyCache {
+    pub fn new(capacity: usize) -> Self {
+        return Self {
+            capacity,
+            bodies: HashMap::new(),
+            usage: VecDeque::new(),
+            next_generation: 0,
+        };
+    }
+
+    pub fn get_capacity(self: &Self) -> usize {
+        return self.capacity;
+    }
+
+    /// Sets the capacity, evicting the least recently used bodies that no longer fit.
+    pub fn set_capacity(self: &mut Self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    /// Returns the cached body and marks it as the most recently used.
+    pub fn get(self: &mut Self, id: Uuid) -> Option<&str> {
+        if !self.bodies.contains_key(&id) {
+            return None;
+        }
+
+        self.touch(id);
+
+        return self.bodies.get(&id).map(|(body, _)| body.as_str());
+    }
+
+    /// Returns the cached body without changing its usage.
+    #[cfg(test)]
+    pub fn peek(self: &Self, id: Uuid) -> Option<&str> {
+        return self.bodies.get(&id).map(|(body, _)| body.as_str());
+    }
+
+    pub fn insert(self: &mut Self, id: Uuid, body: String) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.bodies.insert(id, (body, 0));
+        self.touch(id);
+        self.evict();
+    }
+
+    /// Removes the body, its records in `usage` become stale.
+    pub fn remove(self: &mut Self, id: Uuid) {
+        self.bodies.remove(&id);
+    }
+
+    pub fn clear(self: &mut Self) {
+        self.bodies.clear();
+        self.usage.clear();
+    }
+
+    fn touch(self: &mut Self, id: Uuid) {
+        let Some((_, generation)) = self.bodies.get_mut(&id) else {
+            return;
+        };
+
+        self.next_generation += 1;
+        *generation = self.next_generation;
+        self.usage.push_back((id, self.next_generation));
+
+        // Drop stale records once they outnumber the live ones, which keeps `usage` bounded
+        // and costs O(1) per use on average
+        if self.usage.len() > 2 * self.bodies.len() + 16 {
+            let bodies = &self.bodies;
+            self.usage
+                .retain(|(id, generation)| bodies.get(id).is_some_and(|(_, current)| current == generation));
+        }
+    }
+
+    fn evict(self: &mut Self) {
+        while self.bodies.len() > self.capacity {
+            let Some((id, generation)) = self.usage.pop_front() else {
+                break;
+            };
+
+            // Skip records of bodies that were used again or removed since
+            if self.bodies.get(&id).is_some_and(|(_, current)| *current == generation) {
+                self.bodies.remove(&id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BodyCache;
+    use uuid::Uuid;
+
+    #[test]
+    fn evicts_the_least_recently_used_body() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let mut cache = BodyCache::new(2);
+        cache.insert(a, "a".to_string());
+        cache.insert(b, "b".to_string());
+        assert_eq!(cache.get(a), Some("a"));
+
+        cache.insert(c, "c".to_string());
+        assert_eq!(cache.peek(b), None);
+        assert_eq!(cache.peek(a), Some("a"));
+        assert_eq!(cache.peek(c), Some("c"));
+    }
+
+    #[test]
+    fn removed_and_reinserted_bodies_are_not_evicted_by_stale_uses() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let mut cache = BodyCache::new(2);
+        cache.insert(a, "a".to_string());
+        cache.insert(b, "b".to_string());
+        cache.remove(a);
+        cache.insert(a, "a again".to_string());
+
+        cache.insert(c, "c".to_string());
+        assert_eq!(cache.peek(b), None);
+        assert_eq!(cache.peek(a), Some("a again"));
+    }
+
+    #[test]
+    fn repeated_uses_keep_the_usage_queue_bounded() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+
+        let mut cache = BodyCache::new(4);
+        for id in &ids {
+            cache.insert(*id, id.to_string());
+        }
+        for _ in 0..1000 {
+            for id in &ids {
+                cache.get(*id);
+            }
+        }
+
+        assert!(cache.usage.len() <= 2 * ids.len() + 16 + 1);
+
+        cache.set_capacity(1);
+        assert_eq!(cache.peek(ids[3]), Some(ids[3].to_string().as_str()));
+        assert_eq!(cache.bodies.len(), 1);
+    }
+}
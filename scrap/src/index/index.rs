@@ -1,9 +1,14 @@
-use crate::api::{FolderSummary, NoteSummary};
-use crate::index::{ExtendReport, IndexError, IndexEvent};
+use crate::api::{BrokenLink, FolderNode, FolderSummary, NoteLink, NoteSummary, TitleMatch};
+use crate::index::{BodyCache, ExtendReport, IndexError, IndexEvent, LinkIndex, SearchIndex};
 use crate::model::{Folder, Note};
-
-use std::collections::HashMap;
-use std::path::Path;
+use crate::query::{Query, Term};
+use crate::text::fuzzy_match::{fuzzy_match, normalize};
+use crate::text::normalize_tag::is_tag_within;
+use crate::text::property_value::property_value;
+use crate::text::tokenize::tokenize;
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 pub struct Index {
@@ -11,6 +16,14 @@ pub struct Index {
     /// For searching notes by their title and type
     title_index: HashMap<String, Vec<Uuid>>,
     file_type_index: HashMap<String, Vec<Uuid>>,
+    /// For listing notes by their normalized tags
+    tag_index: HashMap<String, Vec<Uuid>>,
+    /// For full-text search over note titles and bodies
+    search_index: SearchIndex,
+    /// For following `[[wiki links]]` between notes in both directions
+    link_index: LinkIndex,
+    /// Recently used bodies of notes whose body was unloaded
+    body_cache: BodyCache,
 
     folders: HashMap<Uuid, Folder>,
     /// For searching for folders by their display name
@@ -23,6 +36,10 @@ impl Index {
             notes: HashMap::new(),
             title_index: HashMap::new(),
             file_type_index: HashMap::new(),
+            tag_index: HashMap::new(),
+            search_index: SearchIndex::new(),
+            link_index: LinkIndex::new(),
+            body_cache: BodyCache::new(0),
 
             folders: HashMap::new(),
             display_name_index: HashMap::new(),
@@ -83,16 +100,45 @@ impl Index {
         return Ok(report);
     }
 
-    pub fn insert_note(self: &mut Self, note: Note) -> Result<(), IndexError> {
+    pub fn insert_note(self: &mut Self, mut note: Note) -> Result<(), IndexError> {
         let id = note.get_id();
 
-        if let Some(note) = self.notes.get(&id) {
+        if self.notes.contains_key(&id) {
             return Err(IndexError::IdConflict(id));
         }
 
         let title = note.get_title().to_string();
         let file_type = note.get_file_type().to_string();
 
+        // Register the note with its parent folder
+        if let Some(parent) = self.folders.get_mut(&note.get_parent_id()) {
+            parent.add_child_note(id);
+        }
+
+        // Scanned notes come with their body's index data, so the body doesn't have to be read again
+        match note.take_indexed_body() {
+            Some(indexed_body) => {
+                let mut frequencies: HashMap<String, u32> = HashMap::new();
+                for term in tokenize(note.get_title()) {
+                    *frequencies.entry(term).or_default() += 1;
+                }
+                for (term, frequency) in indexed_body.terms {
+                    *frequencies.entry(term).or_default() += frequency;
+                }
+
+                self.search_index.insert_terms(id, frequencies);
+                self.link_index.insert_targets(id, indexed_body.links);
+            }
+            None => {
+                self.search_index.insert(id, &Self::searchable_content(&note));
+                self.link_index.insert(id, note.get_body().unwrap_or_default());
+            }
+        }
+
+        for tag in note.get_tags() {
+            self.tag_index.entry(tag.clone()).or_default().push(id);
+        }
+
         self.notes.insert(id, note);
         self.title_index.entry(title).or_default().push(id);
         self.file_type_index.entry(file_type).or_default().push(id);
@@ -103,12 +149,17 @@ impl Index {
     pub fn insert_folder(self: &mut Self, folder: Folder) -> Result<(), IndexError> {
         let id = folder.get_id();
 
-        if let Some(folder) = self.folders.get(&id) {
+        if self.folders.contains_key(&id) {
             return Err(IndexError::IdConflict(id));
         }
 
         let display_name = folder.get_display_name().to_string();
 
+        // Register the folder with its parent folder
+        if let Some(parent) = self.folders.get_mut(&folder.get_parent_id()) {
+            parent.add_child_folder(id);
+        }
+
         self.folders.insert(id, folder);
         self.display_name_index.entry(display_name).or_default().push(id);
 
@@ -118,6 +169,10 @@ impl Index {
     pub fn remove_note(self: &mut Self, id: Uuid) -> Result<Note, IndexError> {
         let note = self.notes.remove(&id).ok_or(IndexError::NoteNotFound(id))?;
 
+        if let Some(parent) = self.folders.get_mut(&note.get_parent_id()) {
+            parent.remove_child_note(id);
+        }
+
         let title = note.get_title();
         if let Some(ids) = self.title_index.get_mut(title) {
             ids.retain(|v| *v != id);
@@ -136,14 +191,258 @@ impl Index {
             }
         }
 
+        for tag in note.get_tags() {
+            if let Some(ids) = self.tag_index.get_mut(tag) {
+                ids.retain(|v| *v != id);
+
+                if ids.is_empty() {
+                    self.tag_index.remove(tag);
+                }
+            }
+        }
+
+        self.search_index.remove(id);
+        self.link_index.remove(id);
+        self.body_cache.remove(id);
+
         return Ok(note);
     }
 
-    /// TODO: This function would remove the folder and relocate child notes to parent directory
+    /// Updates the note's title and file path, keeping `title_index` in sync.
+    /// The body has to be loaded, since the note is indexed for search again.
+    pub fn rename_note(self: &mut Self, id: Uuid, new_title: &str, new_path: PathBuf) -> Result<(), IndexError> {
+        let note = self.notes.get_mut(&id).ok_or(IndexError::NoteNotFound(id))?;
+
+        let old_title = note.get_title().to_string();
+        if let Some(ids) = self.title_index.get_mut(&old_title) {
+            ids.retain(|v| *v != id);
+
+            if ids.is_empty() {
+                self.title_index.remove(&old_title);
+            }
+        }
+
+        note.set_title(new_title);
+        note.set_relative_path(new_path);
+        self.title_index.entry(new_title.to_string()).or_default().push(id);
+        self.search_index.insert(id, &Self::searchable_content(note));
+
+        return Ok(());
+    }
+
+    /// Replaces the note's body and marks it as dirty, keeping the search and link indexes in sync.
+    pub fn update_note_body(self: &mut Self, id: Uuid, body: &str) -> Result<(), IndexError> {
+        let note = self.notes.get_mut(&id).ok_or(IndexError::NoteNotFound(id))?;
+
+        note.write_all(body);
+        self.search_index.insert(id, &Self::searchable_content(note));
+        self.link_index.insert(id, body);
+        self.body_cache.remove(id);
+
+        return Ok(());
+    }
+
+    /// Adds an already normalized tag to the note, returns false if the note has it already.
+    pub fn add_tag(self: &mut Self, id: Uuid, tag: String) -> Result<bool, IndexError> {
+        let note = self.notes.get_mut(&id).ok_or(IndexError::NoteNotFound(id))?;
+
+        if !note.add_tag(tag.clone()) {
+            return Ok(false);
+        }
+
+        self.tag_index.entry(tag).or_default().push(id);
+
+        return Ok(true);
+    }
+
+    /// Removes the tag from the note, returns false if the note didn't have it.
+    pub fn remove_tag(self: &mut Self, id: Uuid, tag: &str) -> Result<bool, IndexError> {
+        let note = self.notes.get_mut(&id).ok_or(IndexError::NoteNotFound(id))?;
+
+        if !note.remove_tag(tag) {
+            return Ok(false);
+        }
+
+        if let Some(ids) = self.tag_index.get_mut(tag) {
+            ids.retain(|v| *v != id);
+
+            if ids.is_empty() {
+                self.tag_index.remove(tag);
+            }
+        }
+
+        return Ok(true);
+    }
+
+    /// Returns the note's body if it is held in memory or in the body cache.
+    pub fn get_loaded_body(self: &mut Self, id: Uuid) -> Result<Option<String>, IndexError> {
+        let note = self.notes.get(&id).ok_or(IndexError::NoteNotFound(id))?;
+
+        if let Some(body) = note.get_body() {
+            return Ok(Some(body.to_string()));
+        }
+
+        return Ok(self.body_cache.get(id).map(|body| body.to_string()));
+    }
+
+    /// Gives the note back its body as read from disk.
+    pub fn load_body(self: &mut Self, id: Uuid, body: String) -> Result<(), IndexError> {
+        let note = self.notes.get_mut(&id).ok_or(IndexError::NoteNotFound(id))?;
+
+        note.load_body(body);
+        self.body_cache.remove(id);
+
+        return Ok(());
+    }
+
+    /// Drops the note's body from memory, keeping it in the body cache as the most recently used.
+    /// Bodies with unsaved changes are kept.
+    pub fn unload_body(self: &mut Self, id: Uuid) -> Result<(), IndexError> {
+        let note = self.notes.get_mut(&id).ok_or(IndexError::NoteNotFound(id))?;
+
+        if let Some(body) = note.unload_body() {
+            self.body_cache.insert(id, body);
+        }
+
+        return Ok(());
+    }
+
+    /// Drops the bodies of every note without unsaved changes from memory, bypassing the body cache.
+    pub fn unload_bodies(self: &mut Self) {
+        for note in self.notes.values_mut() {
+            note.unload_body();
+        }
+    }
+
+    /// Caches a body read from disk for a note whose body is unloaded.
+    pub fn cache_body(self: &mut Self, id: Uuid, body: String) {
+        self.body_cache.insert(id, body);
+    }
+
+    /// Returns the IDs of all notes whose body is unloaded.
+    pub fn get_unloaded_note_ids(self: &Self) -> Vec<Uuid> {
+        return self
+            .notes
+            .values()
+            .filter(|n| !n.is_body_loaded())
+            .map(|n| n.get_id())
+            .collect();
+    }
+
+    pub fn get_body_cache_capacity(self: &Self) -> usize {
+        return self.body_cache.get_capacity();
+    }
+
+    pub fn set_body_cache_capacity(self: &mut Self, capacity: usize) {
+        self.body_cache.set_capacity(capacity);
+    }
+
+    pub fn clear_body_cache(self: &mut Self) {
+        self.body_cache.clear();
+    }
+
+    /// Moves the note under a new parent folder, keeping both folders' child lists in sync.
+    pub fn move_note(self: &mut Self, id: Uuid, new_parent_id: Uuid, new_path: PathBuf) -> Result<(), IndexError> {
+        let note = self.notes.get_mut(&id).ok_or(IndexError::NoteNotFound(id))?;
+
+        let old_parent_id = note.get_parent_id();
+
+        note.set_parent_id(new_parent_id);
+        note.set_relative_path(new_path);
+
+        if let Some(old_parent) = self.folders.get_mut(&old_parent_id) {
+            old_parent.remove_child_note(id);
+        }
+
+        if let Some(new_parent) = self.folders.get_mut(&new_parent_id) {
+            new_parent.add_child_note(id);
+        }
+
+        return Ok(());
+    }
+
+    /// Updates the folder's display name and directory, keeping `display_name_index`
+    /// and the cached paths of every descendant note and folder in sync.
+    pub fn rename_folder(
+        self: &mut Self,
+        id: Uuid,
+        new_display_name: &str,
+        new_dir: PathBuf,
+    ) -> Result<(), IndexError> {
+        let folder = self.folders.get_mut(&id).ok_or(IndexError::FolderNotFound(id))?;
+
+        let old_display_name = folder.get_display_name().to_string();
+        if let Some(ids) = self.display_name_index.get_mut(&old_display_name) {
+            ids.retain(|v| *v != id);
+
+            if ids.is_empty() {
+                self.display_name_index.remove(&old_display_name);
+            }
+        }
+
+        let old_dir = folder.get_relative_path().to_path_buf();
+
+        folder.set_display_name(new_display_name);
+        folder.set_relative_path(new_dir.clone());
+        self.display_name_index
+            .entry(new_display_name.to_string())
+            .or_default()
+            .push(id);
+
+        self.rebase_descendant_paths(id, &old_dir, &new_dir);
+
+        return Ok(());
+    }
+
+    /// Moves the folder under a new parent folder, keeping both folders' child lists
+    /// and the cached paths of every descendant note and folder in sync.
+    pub fn move_folder(self: &mut Self, id: Uuid, new_parent_id: Uuid, new_dir: PathBuf) -> Result<(), IndexError> {
+        let folder = self.folders.get_mut(&id).ok_or(IndexError::FolderNotFound(id))?;
+
+        let old_parent_id = folder.get_parent_id();
+        let old_dir = folder.get_relative_path().to_path_buf();
+
+        folder.set_parent_id(new_parent_id);
+        folder.set_relative_path(new_dir.clone());
+
+        if let Some(old_parent) = self.folders.get_mut(&old_parent_id) {
+            old_parent.remove_child_folder(id);
+        }
+
+        if let Some(new_parent) = self.folders.get_mut(&new_parent_id) {
+            new_parent.add_child_folder(id);
+        }
+
+        self.rebase_descendant_paths(id, &old_dir, &new_dir);
+
+        return Ok(());
+    }
+
+    /// Returns true if `id` is `ancestor_id` itself or one of its descendant folders.
+    pub fn is_folder_within(self: &Self, id: Uuid, ancestor_id: Uuid) -> bool {
+        let mut current_id = id;
+
+        // Walk up the parent chain until the workspace root is reached
+        while let Some(folder) = self.folders.get(&current_id) {
+            if current_id == ancestor_id {
+                return true;
+            }
+
+            current_id = folder.get_parent_id();
+        }
+
+        return false;
+    }
+
+    /// Removes the folder along with every note and folder inside it.
     pub fn remove_folder(self: &mut Self, id: Uuid) -> Result<Folder, IndexError> {
         // Clear cached folder ids
         let folder = self.folders.remove(&id).ok_or(IndexError::FolderNotFound(id))?;
 
+        if let Some(parent) = self.folders.get_mut(&folder.get_parent_id()) {
+            parent.remove_child_folder(id);
+        }
+
         let display_name = folder.get_display_name();
         if let Some(ids) = self.display_name_index.get_mut(display_name) {
             ids.retain(|v| *v != id);
@@ -166,11 +465,135 @@ impl Index {
         return Ok(folder);
     }
 
+    /// Rewrites the cached paths of every note and folder below `folder_id`
+    /// after the folder's directory moved from `old_dir` to `new_dir`.
+    fn rebase_descendant_paths(self: &mut Self, folder_id: Uuid, old_dir: &Path, new_dir: &Path) {
+        let Some(folder) = self.folders.get(&folder_id) else {
+            return;
+        };
+
+        let child_notes = folder.get_child_notes().clone();
+        let child_folders = folder.get_child_folders().clone();
+
+        for child_note in child_notes {
+            if let Some(note) = self.notes.get_mut(&child_note)
+                && let Ok(rest) = note.get_relative_path().strip_prefix(old_dir)
+            {
+                let new_path = new_dir.join(rest);
+                note.set_relative_path(new_path);
+            }
+        }
+
+        // recursive into child folders
+        for child_folder in child_folders {
+            if let Some(folder) = self.folders.get_mut(&child_folder)
+                && let Ok(rest) = folder.get_relative_path().strip_prefix(old_dir)
+            {
+                let new_path = new_dir.join(rest);
+                folder.set_relative_path(new_path);
+            }
+
+            self.rebase_descendant_paths(child_folder, old_dir, new_dir);
+        }
+    }
+
+    /// Searches note titles and bodies, returning the IDs of matching notes with their scores, best first.
+    pub fn search(self: &Self, query: &str) -> Vec<(Uuid, f32)> {
+        let mut hits = Vec::new();
+
+        for (id, score) in self.search_index.search(query) {
+            if self.notes.contains_key(&id) {
+                hits.push((id, score));
+            } else if cfg!(debug_assertions) {
+                eprintln!("Database Error: found orphan id {} inside search_index!", id);
+            }
+        }
+
+        return hits;
+    }
+
+    /// Returns the notes matching every clause of the query, sorted by title.
+    /// The workspace root is not stored in the index, `in:` clauses resolve it by `root_id`.
+    pub fn query(self: &Self, query: &Query, root_id: Uuid) -> Vec<NoteSummary> {
+        // Resolve text and folder clauses once, instead of once per note
+        let mut text_hits: Vec<HashSet<Uuid>> = Vec::new();
+        let mut folder_ids: Vec<Vec<Uuid>> = Vec::new();
+
+        for clause in &query.clauses {
+            match &clause.term {
+                Term::Text(text) => {
+                    text_hits.push(self.search_index.search(text).into_iter().map(|(id, _)| id).collect());
+                }
+                Term::Folder(folder) => folder_ids.push(self.resolve_folder_reference(folder, root_id)),
+                _ => {}
+            }
+        }
+
+        let mut results = Vec::new();
+
+        for note in self.notes.values() {
+            let mut text_hits = text_hits.iter();
+            let mut folder_ids = folder_ids.iter();
+
+            let is_match = query.clauses.iter().all(|clause| {
+                let is_term_match = match &clause.term {
+                    Term::Text(_) => text_hits.next().is_some_and(|hits| hits.contains(&note.get_id())),
+                    Term::FileType(file_type) => note.get_file_type() == file_type,
+                    Term::Folder(_) => folder_ids.next().is_some_and(|ids| {
+                        ids.iter()
+                            .any(|id| *id == root_id || self.is_folder_within(note.get_parent_id(), *id))
+                    }),
+                    Term::Tag(tag) => note.get_tags().iter().any(|t| is_tag_within(t, tag)),
+                    Term::Title(title) => note.get_title().to_lowercase().contains(&title.to_lowercase()),
+                    Term::Created(range) => range.contains(note.get_created_at()),
+                    Term::Modified(range) => range.contains(note.get_modified_at()),
+                };
+
+                return is_term_match != clause.negated;
+            });
+
+            if is_match {
+                results.push(NoteSummary::new(
+                    note.get_id(),
+                    note.get_title(),
+                    note.get_file_type(),
+                    note.get_parent_id(),
+                ));
+            }
+        }
+
+        results.sort_by(|a, b| a.title.cmp(&b.title));
+
+        return results;
+    }
+
+    /// Resolves a folder given by ID or by display name, ignoring case, into the matching folder IDs.
+    fn resolve_folder_reference(self: &Self, folder: &str, root_id: Uuid) -> Vec<Uuid> {
+        if let Ok(id) = Uuid::parse_str(folder)
+            && (id == root_id || self.folders.contains_key(&id))
+        {
+            return vec![id];
+        }
+
+        let normalized_name = normalize(folder);
+
+        return self
+            .display_name_index
+            .iter()
+            .filter(|(display_name, _)| normalize(display_name) == normalized_name)
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect();
+    }
+
+    fn searchable_content(note: &Note) -> String {
+        return format!("{}\n{}", note.get_title(), note.get_body().unwrap_or_default());
+    }
+
     pub fn list_notes(self: &Self) -> Result<Vec<NoteSummary>, IndexError> {
         return Ok(self
             .notes
             .values()
-            .map(|n| NoteSummary::new(n.get_id().clone(), n.get_title(), n.get_file_type()))
+            .map(|n| NoteSummary::new(n.get_id(), n.get_title(), n.get_file_type(), n.get_parent_id()))
             .collect());
     }
 
@@ -178,14 +601,109 @@ impl Index {
         return Ok(self
             .folders
             .values()
-            .map(|f| FolderSummary::new(f.get_id().clone(), f.get_display_name()))
+            .map(|f| FolderSummary::new(f.get_id(), f.get_display_name(), f.get_parent_id()))
             .collect());
     }
 
+    /// Builds the tree node of the folder, expanding child folders up to `max_depth` levels deep.
+    /// The workspace root is not stored in the index, its children are looked up by `root_id`.
+    pub fn get_folder_node(
+        self: &Self,
+        id: Uuid,
+        root_id: Uuid,
+        max_depth: Option<usize>,
+    ) -> Result<FolderNode, IndexError> {
+        let (mut node, child_notes, child_folders) = if id == root_id {
+            let child_notes: Vec<Uuid> = self
+                .notes
+                .values()
+                .filter(|n| n.get_parent_id() == root_id)
+                .map(|n| n.get_id())
+                .collect();
+            let child_folders: Vec<Uuid> = self
+                .folders
+                .values()
+                .filter(|f| f.get_parent_id() == root_id)
+                .map(|f| f.get_id())
+                .collect();
+
+            let node = FolderNode::new(
+                root_id,
+                "",
+                Uuid::nil(),
+                "",
+                child_notes.len(),
+                child_folders.len(),
+                Vec::new(),
+            );
+            (node, child_notes, child_folders)
+        } else {
+            let folder = self.folders.get(&id).ok_or(IndexError::FolderNotFound(id))?;
+
+            let child_notes = folder.get_child_notes().clone();
+            let child_folders = folder.get_child_folders().clone();
+
+            let node = FolderNode::new(
+                id,
+                folder.get_display_name(),
+                folder.get_parent_id(),
+                folder.get_relative_path(),
+                child_notes.len(),
+                child_folders.len(),
+                folder
+                    .get_properties()
+                    .iter()
+                    .map(|(key, value)| (key.clone(), property_value(value)))
+                    .collect(),
+            );
+            (node, child_notes, child_folders)
+        };
+
+        if max_depth == Some(0) {
+            return Ok(node);
+        }
+
+        for child_note in child_notes {
+            if let Some(n) = self.notes.get(&child_note) {
+                node.notes.push(NoteSummary::new(
+                    n.get_id(),
+                    n.get_title(),
+                    n.get_file_type(),
+                    n.get_parent_id(),
+                ));
+            }
+        }
+
+        // recursive into child folders
+        for child_folder in child_folders {
+            let child_node = self.get_folder_node(child_folder, root_id, max_depth.map(|d| d - 1))?;
+            node.folders.push(child_node);
+        }
+
+        node.notes.sort_by(|a, b| a.title.cmp(&b.title));
+        node.folders.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+
+        return Ok(node);
+    }
+
     pub fn get_note(self: &Self, id: Uuid) -> Result<&Note, IndexError> {
         return self.notes.get(&id).ok_or(IndexError::NoteNotFound(id));
     }
 
+    pub fn get_note_mut(self: &mut Self, id: Uuid) -> Result<&mut Note, IndexError> {
+        return self.notes.get_mut(&id).ok_or(IndexError::NoteNotFound(id));
+    }
+
+    /// Returns the IDs of all notes with unsaved changes.
+    pub fn get_dirty_note_ids(self: &Self) -> Vec<Uuid> {
+        return self
+            .notes
+            .values()
+            .filter(|n| n.is_dirty())
+            .map(|n| n.get_id())
+            .collect();
+    }
+
     pub fn get_notes_by_title(self: &Self, title: &str) -> Result<Vec<&Note>, IndexError> {
         let ids = self.title_index.get(title).ok_or(IndexError::NotFound)?;
 
@@ -202,6 +720,37 @@ impl Index {
         return Ok(notes);
     }
 
+    /// Finds notes whose title matches the query case-insensitively by exact, prefix,
+    /// substring, subsequence or near-miss matching, ranked best first.
+    pub fn find_notes_by_title(self: &Self, query: &str) -> Vec<TitleMatch> {
+        let normalized_query = normalize(query);
+
+        let mut matches = Vec::new();
+
+        for (title, ids) in &self.title_index {
+            let Some(score) = fuzzy_match(&normalized_query, &normalize(title)) else {
+                continue;
+            };
+
+            for id in ids {
+                if let Some(n) = self.notes.get(id) {
+                    let summary = NoteSummary::new(n.get_id(), n.get_title(), n.get_file_type(), n.get_parent_id());
+                    matches.push(TitleMatch::new(summary, score));
+                } else if cfg!(debug_assertions) {
+                    eprintln!("Database Error: found orphan id {} inside title_index!", id);
+                }
+            }
+        }
+
+        matches.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.note.title.cmp(&b.note.title))
+        });
+
+        return matches;
+    }
+
     pub fn get_notes_by_type(self: &Self, file_type: &str) -> Result<Vec<&Note>, IndexError> {
         let ids = self.file_type_index.get(file_type).ok_or(IndexError::NotFound)?;
 
@@ -218,6 +767,162 @@ impl Index {
         return Ok(notes);
     }
 
+    /// Returns the note's links in the order they appear in its body, resolved against the current notes.
+    pub fn get_outgoing_links(self: &Self, id: Uuid) -> Result<Vec<NoteLink>, IndexError> {
+        if !self.notes.contains_key(&id) {
+            return Err(IndexError::NoteNotFound(id));
+        }
+
+        let mut links = Vec::new();
+
+        for target in self.link_index.get_targets(id) {
+            let note = self
+                .resolve_link(target)
+                .and_then(|linked_id| self.notes.get(&linked_id));
+            let summary =
+                note.map(|n| NoteSummary::new(n.get_id(), n.get_title(), n.get_file_type(), n.get_parent_id()));
+
+            links.push(NoteLink::new(target, summary));
+        }
+
+        return Ok(links);
+    }
+
+    /// Returns the notes linking to the note by its title or ID, sorted by title.
+    pub fn get_backlinks(self: &Self, id: Uuid) -> Result<Vec<NoteSummary>, IndexError> {
+        let note = self.notes.get(&id).ok_or(IndexError::NoteNotFound(id))?;
+
+        let mut source_ids: Vec<Uuid> = self
+            .link_index
+            .get_sources(&id.to_string())
+            .iter()
+            .chain(self.link_index.get_sources(note.get_title()))
+            .copied()
+            .collect();
+
+        source_ids.sort();
+        source_ids.dedup();
+
+        let mut backlinks = Vec::new();
+
+        for source_id in source_ids {
+            // A title link may resolve to another note with the same title
+            let links_to_note = self
+                .link_index
+                .get_targets(source_id)
+                .iter()
+                .any(|target| self.resolve_link(target) == Some(id));
+
+            if !links_to_note {
+                continue;
+            }
+
+            if let Some(n) = self.notes.get(&source_id) {
+                backlinks.push(NoteSummary::new(
+                    n.get_id(),
+                    n.get_title(),
+                    n.get_file_type(),
+                    n.get_parent_id(),
+                ));
+            } else if cfg!(debug_assertions) {
+                eprintln!("Database Error: found orphan id {} inside link_index!", source_id);
+            }
+        }
+
+        backlinks.sort_by(|a, b| a.title.cmp(&b.title));
+
+        return Ok(backlinks);
+    }
+
+    /// Returns every link whose target doesn't match any note, sorted by the linking note's title.
+    pub fn get_broken_links(self: &Self) -> Vec<BrokenLink> {
+        let mut broken_links = Vec::new();
+
+        for (source_id, targets) in self.link_index.iter() {
+            let Some(n) = self.notes.get(source_id) else {
+                continue;
+            };
+
+            for target in targets {
+                if self.resolve_link(target).is_none() {
+                    let source = NoteSummary::new(n.get_id(), n.get_title(), n.get_file_type(), n.get_parent_id());
+                    broken_links.push(BrokenLink::new(source, target));
+                }
+            }
+        }
+
+        broken_links.sort_by(|a, b| {
+            a.source
+                .title
+                .cmp(&b.source.title)
+                .then_with(|| a.target.cmp(&b.target))
+        });
+
+        return broken_links;
+    }
+
+    /// Resolves a link target to a note, first by ID, then by exact title and then by title ignoring case.
+    /// When several notes share the title, the one with the lowest ID is picked so the result is stable.
+    fn resolve_link(self: &Self, target: &str) -> Option<Uuid> {
+        if let Ok(id) = Uuid::parse_str(target) {
+            return self.notes.contains_key(&id).then_some(id);
+        }
+
+        if let Some(ids) = self.title_index.get(target) {
+            return ids.iter().min().copied();
+        }
+
+        let normalized_target = normalize(target);
+
+        return self
+            .title_index
+            .iter()
+            .filter(|(title, _)| normalize(title) == normalized_target)
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .min();
+    }
+
+    /// Returns the notes tagged with `tag` or any tag nested below it, sorted by title.
+    pub fn get_notes_by_tag(self: &Self, tag: &str) -> Vec<NoteSummary> {
+        let mut ids: Vec<Uuid> = self
+            .tag_index
+            .iter()
+            .filter(|(indexed_tag, _)| is_tag_within(indexed_tag, tag))
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect();
+
+        // A note tagged with both `project` and `project/scrap` is listed once
+        ids.sort();
+        ids.dedup();
+
+        let mut notes = Vec::new();
+
+        for id in ids {
+            if let Some(n) = self.notes.get(&id) {
+                notes.push(NoteSummary::new(
+                    n.get_id(),
+                    n.get_title(),
+                    n.get_file_type(),
+                    n.get_parent_id(),
+                ));
+            } else if cfg!(debug_assertions) {
+                eprintln!("Database Error: found orphan id {} inside tag_index!", id);
+            }
+        }
+
+        notes.sort_by(|a, b| a.title.cmp(&b.title));
+
+        return notes;
+    }
+
+    /// Returns every tag in use, sorted so nested tags follow their parent.
+    pub fn get_tags(self: &Self) -> Vec<String> {
+        let mut tags: Vec<String> = self.tag_index.keys().cloned().collect();
+        tags.sort();
+
+        return tags;
+    }
+
     pub fn get_folder(self: &Self, id: Uuid) -> Result<&Folder, IndexError> {
         return self.folders.get(&id).ok_or(IndexError::FolderNotFound(id));
     }
@@ -238,12 +943,6 @@ impl Index {
         return Ok(folders);
     }
 
-    pub fn get_note_body(self: &Self, id: Uuid) -> Result<String, IndexError> {
-        let note = self.notes.get(&id).ok_or(IndexError::NoteNotFound(id))?;
-
-        return Ok(note.get_body().to_string());
-    }
-
     pub fn get_note_directory(self: &Self, id: Uuid) -> Result<&Path, IndexError> {
         return self
             .notes
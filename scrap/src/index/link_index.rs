@@ -0,0 +1,106 @@
+use crate::text::extract_links::extract_links;
+use crate::text::fuzzy_match::normalize;
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Forward and reverse index of the wiki-style links between notes.
+///
+/// Links are stored by their target as written, so they can be resolved against the current
+/// notes when queried and follow renames without re-parsing the linking notes.
+pub struct LinkIndex {
+    /// Link targets of every note, in the order they appear in the body
+    outgoing: HashMap<Uuid, Vec<String>>,
+    /// Notes linking to every normalized target
+    incoming: HashMap<String, Vec<Uuid>>,
+}
+
+impl LinkIndex {
+    pub fn new() -> Self {
+        return Self {
+            outgoing: HashMap::new(),
+            incoming: HashMap::new(),
+        };
+    }
+
+    /// Indexes the links inside the note's body, replacing any previously indexed links of the same note.
+    pub fn insert(self: &mut Self, id: Uuid, body: &str) {
+        let targets = extract_links(body)
+            .into_iter()
+            .map(|target| target.to_string())
+            .collect();
+
+        self.insert_targets(id, targets);
+    }
+
+    /// Indexes the note's link targets as written, replacing any previously indexed links of the same note.
+    pub fn insert_targets(self: &mut Self, id: Uuid, targets: Vec<String>) {
+        self.remove(id);
+
+        let mut distinct_targets: Vec<String> = Vec::new();
+
+        for target in targets {
+            if !distinct_targets.contains(&target) {
+                distinct_targets.push(target);
+            }
+        }
+
+        let targets = distinct_targets;
+
+        if targets.is_empty() {
+            return;
+        }
+
+        for target in &targets {
+            let sources = self.incoming.entry(Self::target_key(target)).or_default();
+
+            if !sources.contains(&id) {
+                sources.push(id);
+            }
+        }
+
+        self.outgoing.insert(id, targets);
+    }
+
+    pub fn remove(self: &mut Self, id: Uuid) {
+        let Some(targets) = self.outgoing.remove(&id) else {
+            return;
+        };
+
+        for target in targets {
+            let key = Self::target_key(&target);
+
+            if let Some(sources) = self.incoming.get_mut(&key) {
+                sources.retain(|v| *v != id);
+
+                if sources.is_empty() {
+                    self.incoming.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// Returns the link targets of the note as written.
+    pub fn get_targets(self: &Self, id: Uuid) -> &[String] {
+        return self.outgoing.get(&id).map(|targets| targets.as_slice()).unwrap_or(&[]);
+    }
+
+    /// Returns the notes with a link whose target normalizes to the same key as `target`.
+    pub fn get_sources(self: &Self, target: &str) -> &[Uuid] {
+        return self
+            .incoming
+            .get(&Self::target_key(target))
+            .map(|sources| sources.as_slice())
+            .unwrap_or(&[]);
+    }
+
+    /// Returns every note with at least one link and its link targets.
+    pub fn iter(self: &Self) -> impl Iterator<Item = (&Uuid, &Vec<String>)> {
+        return self.outgoing.iter();
+    }
+
+    /// Titles are matched ignoring case and whitespace runs, the same as a hyphenated UUID.
+    fn target_key(target: &str) -> String {
+        return normalize(target);
+    }
+}
@@ -1,9 +1,15 @@
+mod body_cache;
 mod extend_report;
 mod index;
 mod index_error;
 mod index_event;
+mod link_index;
+mod search_index;
 
+pub use body_cache::BodyCache;
 pub use extend_report::ExtendReport;
 pub use index::Index;
 pub use index_error::IndexError;
 pub use index_event::IndexEvent;
+pub use link_index::LinkIndex;
+pub use search_index::SearchIndex;
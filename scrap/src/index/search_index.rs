@@ -0,0 +1,205 @@
+use crate::text::tokenize::tokenize;
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// BM25 ranking parameters
+const TERM_SATURATION: f32 = 1.2;
+const LENGTH_NORMALIZATION: f32 = 0.75;
+
+/// Inverted index from search terms to the notes containing them.
+pub struct SearchIndex {
+    /// Term frequencies of every note containing the term
+    postings: HashMap<String, HashMap<Uuid, u32>>,
+    /// Distinct terms of every note, for removing the note from `postings`
+    note_terms: HashMap<Uuid, Vec<String>>,
+    /// Number of terms of every note
+    note_lengths: HashMap<Uuid, u32>,
+    total_length: u64,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        return Self {
+            postings: HashMap::new(),
+            note_terms: HashMap::new(),
+            note_lengths: HashMap::new(),
+            total_length: 0,
+        };
+    }
+
+    /// Indexes the note's content, replacing any previously indexed content of the same note.
+    pub fn insert(self: &mut Self, id: Uuid, content: &str) {
+        let mut frequencies: HashMap<String, u32> = HashMap::new();
+        for term in tokenize(content) {
+            *frequencies.entry(term).or_default() += 1;
+        }
+
+        self.insert_terms(id, frequencies);
+    }
+
+    /// Indexes the note's already counted terms, replacing any previously indexed content of the same note.
+    pub fn insert_terms(self: &mut Self, id: Uuid, frequencies: HashMap<String, u32>) {
+        self.remove(id);
+
+        let note_length = frequencies.values().sum::<u32>();
+
+        let mut distinct_terms = Vec::with_capacity(frequencies.len());
+        for (term, frequency) in frequencies {
+            self.postings.entry(term.clone()).or_default().insert(id, frequency);
+            distinct_terms.push(term);
+        }
+
+        self.note_terms.insert(id, distinct_terms);
+        self.note_lengths.insert(id, note_length);
+        self.total_length += note_length as u64;
+    }
+
+    pub fn remove(self: &mut Self, id: Uuid) {
+        let Some(terms) = self.note_terms.remove(&id) else {
+            return;
+        };
+
+        for term in terms {
+            if let Some(notes) = self.postings.get_mut(&term) {
+                notes.remove(&id);
+
+                if notes.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+
+        if let Some(note_length) = self.note_lengths.remove(&id) {
+            self.total_length -= note_length as u64;
+        }
+    }
+
+    /// Returns the notes containing every term of the query, ranked by BM25 score, best first.
+    pub fn search(self: &Self, query: &str) -> Vec<(Uuid, f32)> {
+        let mut query_terms = tokenize(query);
+        query_terms.sort();
+        query_terms.dedup();
+
+        if query_terms.is_empty() {
+            return Vec::new();
+        }
+
+        // Start from the rarest term to keep the candidate set small
+        let mut term_postings = Vec::with_capacity(query_terms.len());
+        for term in &query_terms {
+            match self.postings.get(term) {
+                Some(notes) => term_postings.push(notes),
+                None => return Vec::new(),
+            }
+        }
+        term_postings.sort_by_key(|notes| notes.len());
+
+        let note_count = self.note_lengths.len() as f32;
+        let average_length = self.total_length as f32 / note_count.max(1.0);
+
+        let mut hits = Vec::new();
+
+        for id in term_postings[0].keys() {
+            if !term_postings.iter().all(|notes| notes.contains_key(id)) {
+                continue;
+            }
+
+            let note_length = self.note_lengths.get(id).copied().unwrap_or_default() as f32;
+
+            let mut score = 0.0;
+            for notes in &term_postings {
+                let frequency = notes[id] as f32;
+                let document_frequency = notes.len() as f32;
+
+                let idf = (1.0 + (note_count - document_frequency + 0.5) / (document_frequency + 0.5)).ln();
+                let length_ratio = note_length / average_length.max(1.0);
+                let norm = TERM_SATURATION * (1.0 - LENGTH_NORMALIZATION + LENGTH_NORMALIZATION * length_ratio);
+
+                score += idf * (frequency * (TERM_SATURATION + 1.0)) / (frequency + norm);
+            }
+
+            hits.push((*id, score));
+        }
+
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        return hits;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SearchIndex;
+    use uuid::Uuid;
+
+    fn ids(hits: &[(Uuid, f32)]) -> Vec<Uuid> {
+        return hits.iter().map(|hit| hit.0).collect();
+    }
+
+    #[test]
+    fn notes_must_contain_every_query_term() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let mut index = SearchIndex::new();
+        index.insert(a, "rust borrow checker");
+        index.insert(b, "rust macros");
+
+        assert_eq!(ids(&index.search("rust checker")), vec![a]);
+        assert!(index.search("rust python").is_empty());
+    }
+
+    #[test]
+    fn higher_term_frequency_ranks_first() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let mut index = SearchIndex::new();
+        index.insert(a, "tea and more tea and even more tea");
+        index.insert(b, "tea and some cake and other snacks");
+
+        assert_eq!(ids(&index.search("tea")), vec![a, b]);
+    }
+
+    #[test]
+    fn shorter_note_ranks_first_for_the_same_frequency() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let mut index = SearchIndex::new();
+        index.insert(
+            a,
+            "garden notes about planting tomatoes peppers beans and onions in spring",
+        );
+        index.insert(b, "garden tomatoes");
+
+        assert_eq!(ids(&index.search("tomatoes")), vec![b, a]);
+    }
+
+    #[test]
+    fn rare_terms_weigh_more_than_common_ones() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let mut index = SearchIndex::new();
+        index.insert(a, "meeting meeting budget");
+        index.insert(b, "meeting budget budget");
+        index.insert(c, "meeting agenda");
+
+        // "budget" is rarer than "meeting", so the note repeating it ranks first
+        assert_eq!(ids(&index.search("meeting budget")), vec![b, a]);
+    }
+
+    #[test]
+    fn reinserting_or_removing_a_note_replaces_its_terms() {
+        let id = Uuid::new_v4();
+
+        let mut index = SearchIndex::new();
+        index.insert(id, "old draft");
+        index.insert(id, "new version");
+
+        assert!(index.search("draft").is_empty());
+        assert_eq!(ids(&index.search("version")), vec![id]);
+
+        index.remove(id);
+        assert!(index.search("version").is_empty());
+        assert_eq!(index.total_length, 0);
+    }
+}
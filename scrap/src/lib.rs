@@ -4,8 +4,10 @@ mod fs;
 mod index;
 mod model;
 mod parser;
+mod query;
 mod text;
 mod workspace;
 
+pub use api::QueryError;
 pub use api::Scrap;
 pub use api::ScrapError;
@@ -0,0 +1,14 @@
+use crate::model::{FileStamp, FolderData};
+
+/// A folder as its metadata file was parsed when the index cache was written.
+pub struct CachedFolder {
+    /// Stamp of the folder's metadata file
+    pub stamp: FileStamp,
+    pub data: FolderData,
+}
+
+impl CachedFolder {
+    pub fn new(stamp: FileStamp, data: FolderData) -> Self {
+        return Self { stamp, data };
+    }
+}
@@ -0,0 +1,21 @@
+use crate::model::{FileStamp, IndexedBody, NoteData};
+
+/// A note as it was parsed when the index cache was written, without its body.
+pub struct CachedNote {
+    pub stamp: FileStamp,
+    /// Unix timestamp in seconds
+    pub created_at: u64,
+    pub data: NoteData,
+    pub indexed_body: IndexedBody,
+}
+
+impl CachedNote {
+    pub fn new(stamp: FileStamp, created_at: u64, data: NoteData, indexed_body: IndexedBody) -> Self {
+        return Self {
+            stamp,
+            created_at,
+            data,
+            indexed_body,
+        };
+    }
+}
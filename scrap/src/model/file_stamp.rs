@@ -0,0 +1,16 @@
+/// State of a file on disk when it was last read, used to tell if a cached entry is still valid.
+#[derive(Clone, Copy)]
+pub struct FileStamp {
+    /// Last modification time in nanoseconds since the Unix epoch
+    pub modified: u64,
+    /// Size in bytes
+    pub size: u64,
+    /// `content_hash` of the file content
+    pub hash: u64,
+}
+
+impl FileStamp {
+    pub fn new(modified: u64, size: u64, hash: u64) -> Self {
+        return Self { modified, size, hash };
+    }
+}
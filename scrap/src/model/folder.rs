@@ -29,13 +29,14 @@ impl Folder {
 
         let display_name = data.display_name.unwrap_or_else(|| {
             relative_path
-                .file_stem()
+                .file_name()
                 .and_then(|s| s.to_str())
                 .unwrap_or("Untitled")
                 .to_string()
         });
 
-        let metadata = FolderMetadata::new(id, display_name);
+        let mut metadata = FolderMetadata::new(id, display_name);
+        metadata.set_properties(data.properties);
 
         return Self {
             metadata,
@@ -47,11 +48,36 @@ impl Folder {
     }
 
     pub fn add_child_note(self: &mut Self, id: Uuid) {
-        self.child_notes.push(id);
+        if !self.child_notes.contains(&id) {
+            self.child_notes.push(id);
+        }
     }
 
     pub fn add_child_folder(self: &mut Self, id: Uuid) {
-        self.child_folders.push(id);
+        if !self.child_folders.contains(&id) {
+            self.child_folders.push(id);
+        }
+    }
+
+    pub fn remove_child_note(self: &mut Self, id: Uuid) {
+        self.child_notes.retain(|v| *v != id);
+    }
+
+    pub fn remove_child_folder(self: &mut Self, id: Uuid) {
+        self.child_folders.retain(|v| *v != id);
+    }
+
+    pub fn clear_children(self: &mut Self) {
+        self.child_notes.clear();
+        self.child_folders.clear();
+    }
+
+    pub fn get_parent_id(self: &Self) -> Uuid {
+        return self.parent_id;
+    }
+
+    pub fn set_parent_id(self: &mut Self, parent_id: Uuid) {
+        self.parent_id = parent_id;
     }
 
     pub fn get_child_notes(self: &Self) -> &Vec<Uuid> {
@@ -66,14 +92,26 @@ impl Folder {
         return self.metadata.get_id();
     }
 
+    pub fn set_id(self: &mut Self, id: Uuid) {
+        self.metadata.set_id(id);
+    }
+
     pub fn get_display_name(self: &Self) -> &str {
         return self.metadata.get_display_name();
     }
 
+    pub fn set_display_name(self: &mut Self, display_name: &str) {
+        self.metadata.set_display_name(display_name);
+    }
+
     pub fn get_relative_path(self: &Self) -> &Path {
         return &self.relative_path;
     }
 
+    pub fn set_relative_path(self: &mut Self, relative_path: PathBuf) {
+        self.relative_path = relative_path;
+    }
+
     pub fn get_metadata_file_dir(self: &Self) -> PathBuf {
         return self.relative_path.join("_metadata.txt");
     }
@@ -82,6 +120,14 @@ impl Folder {
         return self.metadata.compose();
     }
 
+    pub fn get_metadata(self: &Self) -> &FolderMetadata {
+        return &self.metadata;
+    }
+
+    pub fn get_properties(self: &Self) -> &[(String, String)] {
+        return self.metadata.get_properties();
+    }
+
     pub fn set_metadata(self: &mut Self, metadata: FolderMetadata) {
         self.metadata = metadata;
     }
@@ -3,6 +3,8 @@ use uuid::Uuid;
 pub struct FolderData {
     pub id: Option<Uuid>,
     pub display_name: Option<String>,
+    /// Metadata keys this app doesn't use, with their raw values in the order they were written
+    pub properties: Vec<(String, String)>,
 }
 
 impl FolderData {
@@ -10,6 +12,7 @@ impl FolderData {
         return Self {
             id: None,
             display_name: None,
+            properties: Vec::new(),
         };
     }
 }
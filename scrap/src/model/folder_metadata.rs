@@ -1,8 +1,13 @@
+use crate::text::escape::escape;
+
 use uuid::Uuid;
 
+#[derive(Clone)]
 pub struct FolderMetadata {
     id: Uuid,
     display_name: String,
+    /// Metadata keys from other tools with their raw values, written back as they were
+    properties: Vec<(String, String)>,
 }
 
 impl FolderMetadata {
@@ -10,6 +15,7 @@ impl FolderMetadata {
         return Self {
             id,
             display_name: display_name.into(),
+            properties: Vec::new(),
         };
     }
 
@@ -17,14 +23,38 @@ impl FolderMetadata {
         return self.id.clone();
     }
 
+    pub fn set_id(self: &mut Self, id: Uuid) {
+        self.id = id;
+    }
+
     pub fn get_display_name(self: &Self) -> &str {
         return &self.display_name;
     }
 
+    pub fn set_display_name(self: &mut Self, display_name: impl Into<String>) {
+        self.display_name = display_name.into();
+    }
+
+    pub fn get_properties(self: &Self) -> &[(String, String)] {
+        return &self.properties;
+    }
+
+    pub fn set_properties(self: &mut Self, properties: Vec<(String, String)>) {
+        self.properties = properties;
+    }
+
     pub fn compose(self: &Self) -> String {
-        return format!(
-            "---\nid: \"{}\"\ndisplay-name: \"{}\"\n---\n",
-            self.id, self.display_name
+        let mut out = format!(
+            "---\nid: \"{}\"\ndisplay-name: \"{}\"\n",
+            self.id,
+            escape(&self.display_name)
         );
+
+        for (key, value) in &self.properties {
+            out.push_str(&format!("{}:{}\n", key, value));
+        }
+
+        out.push_str("---\n");
+        return out;
     }
 }
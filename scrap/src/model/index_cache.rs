@@ -0,0 +1,166 @@
+use crate::model::{CachedFolder, CachedNote, FileStamp, Folder, LineEnding, Note};
+use crate::text::escape::escape;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Parsed notes and folders from the last scan, keyed by their relative path.
+/// Lets a scan skip reading files that haven't changed since.
+/// Note bodies are never stored, only the links and search terms the indexes need from them.
+pub struct IndexCache {
+    notes: HashMap<PathBuf, CachedNote>,
+    folders: HashMap<PathBuf, CachedFolder>,
+    /// IDs generated for directories without a metadata file, they have nothing else to cache
+    directories: HashMap<PathBuf, Uuid>,
+    /// Set when a scan found a file that changed or wasn't cached
+    is_outdated: bool,
+}
+
+impl IndexCache {
+    /// First line of the cache file, caches written in another format are ignored
+    pub const HEADER: &str = "scrap-index-cache 7";
+
+    pub fn new() -> Self {
+        return Self {
+            notes: HashMap::new(),
+            folders: HashMap::new(),
+            directories: HashMap::new(),
+            is_outdated: false,
+        };
+    }
+
+    pub fn insert_note(self: &mut Self, file_path: PathBuf, note: CachedNote) {
+        self.notes.insert(file_path, note);
+    }
+
+    pub fn insert_folder(self: &mut Self, folder_dir: PathBuf, folder: CachedFolder) {
+        self.folders.insert(folder_dir, folder);
+    }
+
+    pub fn insert_directory(self: &mut Self, folder_dir: PathBuf, id: Uuid) {
+        self.directories.insert(folder_dir, id);
+    }
+
+    /// Removes and returns the cached note, each entry is used at most once per scan.
+    pub fn take_note(self: &mut Self, file_path: &Path) -> Option<CachedNote> {
+        return self.notes.remove(file_path);
+    }
+
+    /// Removes and returns the cached folder, each entry is used at most once per scan.
+    pub fn take_folder(self: &mut Self, folder_dir: &Path) -> Option<CachedFolder> {
+        return self.folders.remove(folder_dir);
+    }
+
+    /// Removes and returns the ID of a directory without metadata, each entry is used at most once per scan.
+    pub fn take_directory(self: &mut Self, folder_dir: &Path) -> Option<Uuid> {
+        return self.directories.remove(folder_dir);
+    }
+
+    /// Returns the ID each cached note and folder had at its path.
+    pub fn cached_ids(self: &Self) -> HashMap<PathBuf, Uuid> {
+        let notes = self
+            .notes
+            .iter()
+            .filter_map(|(path, note)| Some((path.clone(), note.data.id?)));
+        let folders = self
+            .folders
+            .iter()
+            .filter_map(|(path, folder)| Some((path.clone(), folder.data.id?)));
+        let directories = self.directories.iter().map(|(path, id)| (path.clone(), *id));
+
+        return notes.chain(folders).chain(directories).collect();
+    }
+
+    pub fn mark_outdated(self: &mut Self) {
+        self.is_outdated = true;
+    }
+
+    /// Returns true if the cache no longer matches the workspace, either because a file
+    /// changed or wasn't cached, or because cached files were not found by the scan.
+    pub fn is_outdated(self: &Self) -> bool {
+        return self.is_outdated || !self.notes.is_empty() || !self.folders.is_empty() || !self.directories.is_empty();
+    }
+
+    /// Composes the cache file for the scanned notes and folders.
+    /// Items without a stamp or index data are left out and read from disk again on the next scan.
+    /// Folders without a stamped metadata file only keep their ID, so a plain directory keeps the one it was given.
+    pub fn compose(notes: &[Note], folders: &[Folder], stamps: &HashMap<PathBuf, FileStamp>) -> String {
+        let mut out = format!("{}\n", Self::HEADER);
+
+        for folder in folders {
+            let Some(stamp) = stamps.get(folder.get_relative_path()) else {
+                out.push_str("directory\n");
+                out.push_str(&format!(
+                    "path: {}\n",
+                    escape(&folder.get_relative_path().to_string_lossy())
+                ));
+                out.push_str(&format!("id: {}\n", folder.get_id()));
+                out.push_str("end\n");
+                continue;
+            };
+
+            out.push_str("folder\n");
+            Self::push_stamp(&mut out, folder.get_relative_path(), stamp);
+            out.push_str(&format!("id: {}\n", folder.get_id()));
+            out.push_str(&format!("display-name: {}\n", escape(folder.get_display_name())));
+            Self::push_properties(&mut out, folder.get_properties());
+            out.push_str("end\n");
+        }
+
+        for note in notes {
+            let (Some(stamp), Some(indexed_body)) = (stamps.get(note.get_relative_path()), note.get_indexed_body())
+            else {
+                continue;
+            };
+
+            out.push_str("note\n");
+            Self::push_stamp(&mut out, note.get_relative_path(), stamp);
+            out.push_str(&format!("created: {}\n", note.get_created_at()));
+            out.push_str(&format!("id: {}\n", note.get_id()));
+            out.push_str(&format!("title: {}\n", escape(note.get_title())));
+            out.push_str(&format!("type: {}\n", escape(note.get_file_type())));
+
+            for tag in note.get_tags() {
+                out.push_str(&format!("tag: {}\n", escape(tag)));
+            }
+
+            Self::push_properties(&mut out, note.get_properties());
+
+            if note.get_metadata().get_line_ending() == LineEnding::CrLf {
+                out.push_str("line-ending: crlf\n");
+            }
+
+            if note.get_metadata().has_byte_order_mark() {
+                out.push_str("byte-order-mark: true\n");
+            }
+
+            for link in &indexed_body.links {
+                out.push_str(&format!("link: {}\n", escape(link)));
+            }
+
+            // Terms are words, CJK characters or CJK bigrams, they never contain whitespace
+            for (term, frequency) in &indexed_body.terms {
+                out.push_str(&format!("term: {} {}\n", frequency, term));
+            }
+
+            out.push_str("end\n");
+        }
+
+        return out;
+    }
+
+    /// Keys never contain `:`, the first one separates the key from the value.
+    fn push_properties(out: &mut String, properties: &[(String, String)]) {
+        for (key, value) in properties {
+            out.push_str(&format!("property: {}:{}\n", escape(key), escape(value)));
+        }
+    }
+
+    fn push_stamp(out: &mut String, path: &Path, stamp: &FileStamp) {
+        out.push_str(&format!("path: {}\n", escape(&path.to_string_lossy())));
+        out.push_str(&format!("modified: {}\n", stamp.modified));
+        out.push_str(&format!("size: {}\n", stamp.size));
+        out.push_str(&format!("hash: {}\n", stamp.hash));
+    }
+}
@@ -0,0 +1,42 @@
+use crate::text::extract_links::extract_links;
+use crate::text::tokenize::tokenize;
+
+use std::collections::HashMap;
+
+/// What the search and link indexes need from a note's body, so the body itself
+/// doesn't have to be held in memory or stored in the index cache.
+pub struct IndexedBody {
+    /// Link targets in the order they first appear
+    pub links: Vec<String>,
+    /// Search terms with the number of times they appear
+    pub terms: Vec<(String, u32)>,
+}
+
+impl IndexedBody {
+    pub fn new() -> Self {
+        return Self {
+            links: Vec::new(),
+            terms: Vec::new(),
+        };
+    }
+
+    pub fn from_body(body: &str) -> Self {
+        let mut links: Vec<String> = Vec::new();
+        for target in extract_links(body) {
+            if !links.iter().any(|link| link == target) {
+                links.push(target.to_string());
+            }
+        }
+
+        let mut frequencies: HashMap<String, u32> = HashMap::new();
+        for term in tokenize(body) {
+            *frequencies.entry(term).or_default() += 1;
+        }
+
+        // Sorted so the index cache is written the same way every time
+        let mut terms: Vec<(String, u32)> = frequencies.into_iter().collect();
+        terms.sort();
+
+        return Self { links, terms };
+    }
+}
@@ -0,0 +1,16 @@
+/// Line break style of a note file, kept so saving a note doesn't change it.
+#[derive(Clone, Copy, PartialEq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    /// Detects the style from the first line break, text without any is `Lf`.
+    pub fn detect(s: &str) -> Self {
+        match s.find('\n') {
+            Some(index) if s[..index].ends_with('\r') => return Self::CrLf,
+            _ => return Self::Lf,
+        }
+    }
+}
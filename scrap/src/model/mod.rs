@@ -1,13 +1,29 @@
+mod cached_folder;
+mod cached_note;
+mod file_stamp;
 mod folder;
 mod folder_data;
 mod folder_metadata;
+mod index_cache;
+mod indexed_body;
+mod line_ending;
 mod note;
 mod note_data;
 mod note_metadata;
+mod trash_entry;
+mod workspace_settings;
 
+pub use cached_folder::CachedFolder;
+pub use cached_note::CachedNote;
+pub use file_stamp::FileStamp;
 pub use folder::Folder;
 pub use folder_data::FolderData;
 pub use folder_metadata::FolderMetadata;
+pub use index_cache::IndexCache;
+pub use indexed_body::IndexedBody;
+pub use line_ending::LineEnding;
 pub use note::Note;
 pub use note_data::NoteData;
 pub use note_metadata::NoteMetadata;
+pub use trash_entry::TrashEntry;
+pub use workspace_settings::WorkspaceSettings;
@@ -1,5 +1,7 @@
+use crate::model::IndexedBody;
 use crate::model::NoteData;
 use crate::model::NoteMetadata;
+use crate::text::normalize_tag::normalize_tag;
 
 use std::path::Path;
 use std::path::PathBuf;
@@ -8,24 +10,34 @@ use uuid::Uuid;
 pub struct Note {
     relative_path: PathBuf,
     metadata: NoteMetadata,
-    body: String,
+    parent_id: Uuid,
+    /// `None` while the body is only stored on disk
+    body: Option<String>,
+    /// Set while scanning, until the index takes it in place of reading the body
+    indexed_body: Option<IndexedBody>,
     is_dirty: bool,
     is_deleted: bool,
-    // TODO: Need a last modified var so we can sync without opening the file...
+    /// Unix timestamps in seconds
+    created_at: u64,
+    modified_at: u64,
 }
 
 impl Note {
-    pub fn new(relative_path: PathBuf, metadata: NoteMetadata) -> Self {
+    pub fn new(relative_path: PathBuf, metadata: NoteMetadata, parent_id: Uuid) -> Self {
         return Self {
             relative_path,
             metadata,
-            body: String::new(),
+            parent_id,
+            body: Some(String::new()),
+            indexed_body: None,
             is_dirty: false,
             is_deleted: false,
+            created_at: 0,
+            modified_at: 0,
         };
     }
 
-    pub fn from_data(relative_path: PathBuf, data: NoteData) -> Self {
+    pub fn from_data(relative_path: PathBuf, data: NoteData, parent_id: Uuid) -> Self {
         let id = data.id.unwrap_or_else(Uuid::new_v4);
 
         let title = data.title.unwrap_or_else(|| {
@@ -39,48 +51,170 @@ impl Note {
         // TODO: resolve file type by detecting it
         let file_type = data.file_type.unwrap_or("rich-text".to_string());
 
-        let metadata = NoteMetadata::new(id, title, file_type);
+        let mut metadata = NoteMetadata::new(id, title, file_type);
+
+        // Tags written by hand may not be normalized, ones that can't be are kept as written
+        // so saving the note doesn't drop them
+        for tag in &data.tags {
+            let tag = normalize_tag(tag).unwrap_or(tag.trim().to_string());
+
+            if !tag.is_empty() {
+                metadata.add_tag(tag);
+            }
+        }
+
+        metadata.set_properties(data.properties);
+        metadata.set_line_ending(data.line_ending);
+        metadata.set_byte_order_mark(data.has_byte_order_mark);
 
         return Self {
             relative_path,
             metadata,
-            body: data.body,
+            parent_id,
+            body: Some(data.body),
+            indexed_body: None,
             is_dirty: false,
             is_deleted: false,
+            created_at: 0,
+            modified_at: 0,
         };
     }
 
+    pub fn get_metadata(self: &Self) -> &NoteMetadata {
+        return &self.metadata;
+    }
+
     pub fn get_relative_path(self: &Self) -> &Path {
         return &self.relative_path;
     }
 
+    pub fn set_relative_path(self: &mut Self, relative_path: PathBuf) {
+        self.relative_path = relative_path;
+    }
+
     pub fn get_id(self: &Self) -> Uuid {
         return self.metadata.get_id();
     }
 
+    pub fn set_id(self: &mut Self, id: Uuid) {
+        self.metadata.set_id(id);
+    }
+
+    pub fn get_parent_id(self: &Self) -> Uuid {
+        return self.parent_id;
+    }
+
+    pub fn set_parent_id(self: &mut Self, parent_id: Uuid) {
+        self.parent_id = parent_id;
+    }
+
     pub fn get_title(self: &Self) -> &str {
         return self.metadata.get_title();
     }
 
+    pub fn set_title(self: &mut Self, title: &str) {
+        self.metadata.set_title(title);
+    }
+
     pub fn get_file_type(self: &Self) -> &str {
         return self.metadata.get_file_type();
     }
 
-    pub fn get_body(self: &Self) -> &str {
-        return &self.body;
+    pub fn get_tags(self: &Self) -> &[String] {
+        return self.metadata.get_tags();
+    }
+
+    /// Adds an already normalized tag and marks the note as dirty if it didn't have it.
+    pub fn add_tag(self: &mut Self, tag: String) -> bool {
+        let is_added = self.metadata.add_tag(tag);
+        self.is_dirty |= is_added;
+
+        return is_added;
+    }
+
+    /// Removes the tag and marks the note as dirty if it had it.
+    pub fn remove_tag(self: &mut Self, tag: &str) -> bool {
+        let is_removed = self.metadata.remove_tag(tag);
+        self.is_dirty |= is_removed;
+
+        return is_removed;
+    }
+
+    pub fn get_properties(self: &Self) -> &[(String, String)] {
+        return self.metadata.get_properties();
+    }
+
+    pub fn get_created_at(self: &Self) -> u64 {
+        return self.created_at;
+    }
+
+    pub fn get_modified_at(self: &Self) -> u64 {
+        return self.modified_at;
+    }
+
+    pub fn set_timestamps(self: &mut Self, created_at: u64, modified_at: u64) {
+        self.created_at = created_at;
+        self.modified_at = modified_at;
+    }
+
+    /// Returns the body, or `None` if it was unloaded and is only stored on disk.
+    pub fn get_body(self: &Self) -> Option<&str> {
+        return self.body.as_deref();
+    }
+
+    pub fn is_body_loaded(self: &Self) -> bool {
+        return self.body.is_some();
+    }
+
+    /// Sets the body as loaded from disk, without marking the note as dirty.
+    pub fn load_body(self: &mut Self, body: String) {
+        self.body = Some(body);
+    }
+
+    /// Drops the body from memory and returns it. Bodies with unsaved changes are kept.
+    pub fn unload_body(self: &mut Self) -> Option<String> {
+        if self.is_dirty {
+            return None;
+        }
+
+        return self.body.take();
+    }
+
+    pub fn get_indexed_body(self: &Self) -> Option<&IndexedBody> {
+        return self.indexed_body.as_ref();
+    }
+
+    pub fn set_indexed_body(self: &mut Self, indexed_body: IndexedBody) {
+        self.indexed_body = Some(indexed_body);
+    }
+
+    pub fn take_indexed_body(self: &mut Self) -> Option<IndexedBody> {
+        return self.indexed_body.take();
+    }
+
+    pub fn is_dirty(self: &Self) -> bool {
+        return self.is_dirty;
+    }
+
+    pub fn mark_as_saved(self: &mut Self) {
+        self.is_dirty = false;
     }
 
     pub fn mark_as_deleted(self: &mut Self) {
         self.is_deleted = true;
     }
 
-    pub fn compose(self: &Self) -> String {
+    /// Composes the file content, or returns `None` if the body isn't loaded.
+    pub fn compose(self: &Self) -> Option<String> {
+        let body = self.body.as_ref()?;
+
         let mut out = self.metadata.compose();
-        out.push_str(&self.body);
-        return out;
+        out.push_str(body);
+        return Some(out);
     }
 
     pub fn write_all(self: &mut Self, content: &str) {
-        self.body = content.to_string();
+        self.body = Some(content.to_string());
+        self.is_dirty = true;
     }
 }
@@ -1,9 +1,16 @@
+use crate::model::LineEnding;
+
 use uuid::Uuid;
 
 pub struct NoteData {
     pub id: Option<Uuid>,
     pub title: Option<String>,
     pub file_type: Option<String>,
+    pub tags: Vec<String>,
+    /// Front matter keys this app doesn't use, with their raw values in the order they were written
+    pub properties: Vec<(String, String)>,
+    pub line_ending: LineEnding,
+    pub has_byte_order_mark: bool,
     pub body: String,
 }
 
@@ -13,6 +20,10 @@ impl NoteData {
             id: None,
             title: None,
             file_type: None,
+            tags: Vec::new(),
+            properties: Vec::new(),
+            line_ending: LineEnding::Lf,
+            has_byte_order_mark: false,
             body: String::new(),
         };
     }
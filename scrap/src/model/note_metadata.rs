@@ -1,9 +1,22 @@
+use crate::model::LineEnding;
+use crate::text::escape::escape;
+
 use uuid::Uuid;
 
+#[derive(Clone)]
 pub struct NoteMetadata {
     id: Uuid,
     title: String,
     file_type: String,
+    /// Tags in the order they were added, nested tags are separated by `/`.
+    /// Tags read from front matter that can't be normalized are kept as written
+    tags: Vec<String>,
+    /// Front matter keys from other tools with their raw values, written back as they were
+    properties: Vec<(String, String)>,
+    /// The front matter is written with the same line breaks as the body
+    line_ending: LineEnding,
+    /// Files that started with a byte order mark are written with one
+    has_byte_order_mark: bool,
 }
 
 impl NoteMetadata {
@@ -12,6 +25,10 @@ impl NoteMetadata {
             id,
             title: title.into(),
             file_type: file_type.into().to_ascii_lowercase(),
+            tags: Vec::new(),
+            properties: Vec::new(),
+            line_ending: LineEnding::Lf,
+            has_byte_order_mark: false,
         }
     }
 
@@ -19,20 +36,98 @@ impl NoteMetadata {
         return self.id.clone();
     }
 
+    pub fn set_id(self: &mut Self, id: Uuid) {
+        self.id = id;
+    }
+
     pub fn get_title(self: &Self) -> &str {
         return &self.title;
     }
 
+    pub fn set_title(self: &mut Self, title: impl Into<String>) {
+        self.title = title.into();
+    }
+
     pub fn get_file_type(self: &Self) -> &str {
         return &self.file_type;
     }
 
+    pub fn get_tags(self: &Self) -> &[String] {
+        return &self.tags;
+    }
+
+    /// Adds an already normalized tag, returns false if the note has it already.
+    pub fn add_tag(self: &mut Self, tag: String) -> bool {
+        if self.tags.contains(&tag) {
+            return false;
+        }
+
+        self.tags.push(tag);
+        return true;
+    }
+
+    /// Removes the tag, returns false if the note didn't have it.
+    pub fn remove_tag(self: &mut Self, tag: &str) -> bool {
+        let count = self.tags.len();
+        self.tags.retain(|t| t != tag);
+
+        return self.tags.len() != count;
+    }
+
+    pub fn get_properties(self: &Self) -> &[(String, String)] {
+        return &self.properties;
+    }
+
+    pub fn set_properties(self: &mut Self, properties: Vec<(String, String)>) {
+        self.properties = properties;
+    }
+
+    pub fn get_line_ending(self: &Self) -> LineEnding {
+        return self.line_ending;
+    }
+
+    pub fn set_line_ending(self: &mut Self, line_ending: LineEnding) {
+        self.line_ending = line_ending;
+    }
+
+    pub fn has_byte_order_mark(self: &Self) -> bool {
+        return self.has_byte_order_mark;
+    }
+
+    pub fn set_byte_order_mark(self: &mut Self, has_byte_order_mark: bool) {
+        self.has_byte_order_mark = has_byte_order_mark;
+    }
+
     pub fn compose(self: &Self) -> String {
-        return format!(
-            "---\nid: \"{}\"\ntitle: \"{}\"\ntype: \"{}\"\n---\n",
+        let mut out = String::new();
+
+        if self.has_byte_order_mark {
+            out.push('\u{feff}');
+        }
+
+        out.push_str(&format!(
+            "---\nid: \"{}\"\ntitle: \"{}\"\ntype: \"{}\"\n",
             self.id.to_string(),
-            self.title,
-            self.file_type
-        );
+            escape(&self.title),
+            escape(&self.file_type)
+        ));
+
+        // Notes without tags keep the original three key front matter
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self.tags.iter().map(|tag| format!("\"{}\"", escape(tag))).collect();
+            out.push_str(&format!("tags: [{}]\n", tags.join(", ")));
+        }
+
+        for (key, value) in &self.properties {
+            out.push_str(&format!("{}:{}\n", key, value));
+        }
+
+        out.push_str("---\n");
+
+        if self.line_ending == LineEnding::CrLf {
+            return out.replace('\n', "\r\n");
+        }
+
+        return out;
     }
 }
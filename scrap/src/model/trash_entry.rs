@@ -0,0 +1,83 @@
+use crate::text::escape::escape;
+
+use std::path::Path;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Records where a trashed item came from so it can be restored later.
+pub struct TrashEntry {
+    id: Uuid,
+    name: String,
+    parent_id: Uuid,
+    original_path: PathBuf,
+    trash_path: PathBuf,
+    /// Unix timestamp in seconds
+    deleted_at: u64,
+    /// Folders are trashed as a whole directory along with everything inside
+    is_folder: bool,
+}
+
+impl TrashEntry {
+    pub fn new(
+        id: Uuid,
+        name: impl Into<String>,
+        parent_id: Uuid,
+        original_path: PathBuf,
+        trash_path: PathBuf,
+        deleted_at: u64,
+        is_folder: bool,
+    ) -> Self {
+        return Self {
+            id,
+            name: name.into(),
+            parent_id,
+            original_path,
+            trash_path,
+            deleted_at,
+            is_folder,
+        };
+    }
+
+    pub fn get_id(self: &Self) -> Uuid {
+        return self.id;
+    }
+
+    pub fn get_name(self: &Self) -> &str {
+        return &self.name;
+    }
+
+    pub fn get_parent_id(self: &Self) -> Uuid {
+        return self.parent_id;
+    }
+
+    pub fn get_original_path(self: &Self) -> &Path {
+        return &self.original_path;
+    }
+
+    pub fn get_trash_path(self: &Self) -> &Path {
+        return &self.trash_path;
+    }
+
+    pub fn get_deleted_at(self: &Self) -> u64 {
+        return self.deleted_at;
+    }
+
+    pub fn is_folder(self: &Self) -> bool {
+        return self.is_folder;
+    }
+
+    pub fn compose(self: &Self) -> String {
+        let kind = if self.is_folder { "folder" } else { "note" };
+
+        return format!(
+            "---\nid: \"{}\"\nkind: \"{}\"\nname: \"{}\"\nparent-id: \"{}\"\noriginal-path: \"{}\"\ntrash-path: \"{}\"\ndeleted-at: \"{}\"\n---\n",
+            self.id,
+            kind,
+            escape(&self.name),
+            self.parent_id,
+            escape(&self.original_path.to_string_lossy()),
+            escape(&self.trash_path.to_string_lossy()),
+            self.deleted_at
+        );
+    }
+}
@@ -0,0 +1,25 @@
+/// Settings stored per workspace in the `_workspace.txt` file at the workspace root.
+#[derive(Clone)]
+pub struct WorkspaceSettings {
+    /// Trashed items older than this many days are purged by `purge_expired_trash`
+    pub trash_retention_days: Option<u64>,
+}
+
+impl WorkspaceSettings {
+    pub fn new() -> Self {
+        return Self {
+            trash_retention_days: None,
+        };
+    }
+
+    pub fn compose(self: &Self) -> String {
+        let mut out = String::from("---\n");
+
+        if let Some(days) = self.trash_retention_days {
+            out.push_str(&format!("trash-retention-days: \"{}\"\n", days));
+        }
+
+        out.push_str("---\n");
+        return out;
+    }
+}
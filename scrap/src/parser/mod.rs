@@ -1,2 +1,6 @@
 pub mod parse_folder;
+pub mod parse_index_cache;
 pub mod parse_note;
+pub mod parse_query;
+pub mod parse_trash;
+pub mod parse_workspace_settings;
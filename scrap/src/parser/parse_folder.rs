@@ -1,12 +1,17 @@
 use crate::model::{Folder, FolderData};
 use crate::text::extract_quoted::extract_quoted;
+use crate::text::property_value::property_value;
 
 use std::str::FromStr;
 use uuid::Uuid;
 
-pub fn parse_folder(input: String) -> FolderData {
+/// Returns `None` if the metadata has an ID that can't be read.
+pub fn parse_folder(input: String) -> Option<FolderData> {
     let mut out_data = FolderData::new();
 
+    // Index of the last unknown key, the lines continuing its value are added to it
+    let mut last_property = None;
+
     for line in input.lines() {
         let trimmed = line.trim();
 
@@ -14,10 +19,39 @@ pub fn parse_folder(input: String) -> FolderData {
             continue;
         }
 
+        // Indented lines and list items continue the value above them, like a YAML list
+        if line.starts_with(char::is_whitespace) || line.starts_with('-') || !trimmed.contains(':') {
+            if let Some(index) = last_property {
+                let (_, value): &mut (String, String) = &mut out_data.properties[index];
+                value.push('\n');
+                value.push_str(line.trim_end());
+            }
+            continue;
+        }
+
+        last_property = None;
+
         if let Some((key, value)) = trimmed.split_once(':') {
-            let Some(extracted_value) = extract_quoted(value) else {
-                // No value found inside qouted, invalid
+            // Keys from other tools are kept as written, so rewriting the metadata doesn't drop them
+            if !matches!(key.trim(), "id" | "display-name") {
+                out_data.properties.push((key.trim().to_string(), value.to_string()));
+                last_property = Some(out_data.properties.len() - 1);
                 continue;
+            }
+
+            let extracted_value = match extract_quoted(value) {
+                Some(extracted_value) => extracted_value,
+                // Values written by hand may be unquoted, they're read like the values of unknown keys
+                None if !value.trim().is_empty() => property_value(value),
+                None => {
+                    // An unreadable ID would be replaced by a new one, so the folder is rejected instead
+                    if key.trim() == "id" {
+                        return None;
+                    }
+
+                    // No value found, invalid
+                    continue;
+                }
             };
 
             // Empty values are invalid
@@ -26,16 +60,12 @@ pub fn parse_folder(input: String) -> FolderData {
             }
 
             match key.trim() {
-                "id" => {
-                    if let Ok(id) = Uuid::from_str(extracted_value) {
-                        out_data.id = Some(id);
-                    }
-                }
-                "display-name" => out_data.display_name = Some(extracted_value.to_string()),
+                "id" => out_data.id = Some(Uuid::from_str(&extracted_value).ok()?),
+                "display-name" => out_data.display_name = Some(extracted_value),
                 _ => {}
             }
         }
     }
 
-    return out_data;
+    return Some(out_data);
 }
@@ -0,0 +1,140 @@
+use crate::model::{CachedFolder, CachedNote, FileStamp, FolderData, IndexCache, IndexedBody, LineEnding, NoteData};
+use crate::text::escape::unescape;
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Parses the index cache file.
+/// Entries are `note`, `folder` or `directory` blocks of `key: value` lines closed by `end`.
+/// Parsing stops at the first malformed entry and incomplete entries are skipped,
+/// a stale or broken cache only costs a re-read.
+pub fn parse_index_cache(input: String) -> IndexCache {
+    let mut out_cache = IndexCache::new();
+
+    let mut rest = input.as_str();
+
+    if next_line(&mut rest) != Some(IndexCache::HEADER) {
+        return out_cache;
+    }
+
+    while let Some(kind) = next_line(&mut rest) {
+        let mut path = None;
+        let mut modified = None;
+        let mut size = None;
+        let mut hash = None;
+        let mut created_at = None;
+        let mut id = None;
+        let mut title = None;
+        let mut file_type = None;
+        let mut display_name = None;
+        let mut tags = Vec::new();
+        let mut properties = Vec::new();
+        let mut line_ending = LineEnding::Lf;
+        let mut has_byte_order_mark = false;
+        let mut indexed_body = IndexedBody::new();
+
+        loop {
+            let Some(line) = next_line(&mut rest) else {
+                // Truncated cache, drop the unfinished entry
+                return out_cache;
+            };
+
+            if line == "end" {
+                break;
+            }
+
+            let Some((key, value)) = line.split_once(": ") else {
+                return out_cache;
+            };
+
+            match key {
+                "path" => path = Some(PathBuf::from(unescape(value))),
+                "modified" => modified = value.parse::<u64>().ok(),
+                "size" => size = value.parse::<u64>().ok(),
+                "hash" => hash = value.parse::<u64>().ok(),
+                "created" => created_at = value.parse::<u64>().ok(),
+                "id" => id = Uuid::from_str(value).ok(),
+                "title" => title = Some(unescape(value)),
+                "type" => file_type = Some(unescape(value)),
+                "display-name" => display_name = Some(unescape(value)),
+                "tag" => tags.push(unescape(value)),
+                "line-ending" if value == "crlf" => line_ending = LineEnding::CrLf,
+                "byte-order-mark" => has_byte_order_mark = value == "true",
+                "property" => {
+                    if let Some((key, value)) = value.split_once(':') {
+                        properties.push((unescape(key), unescape(value)));
+                    }
+                }
+                "link" => indexed_body.links.push(unescape(value)),
+                "term" => {
+                    let Some((frequency, term)) = value.split_once(' ') else {
+                        return out_cache;
+                    };
+                    let Ok(frequency) = frequency.parse::<u32>() else {
+                        return out_cache;
+                    };
+
+                    indexed_body.terms.push((term.to_string(), frequency));
+                }
+                _ => {}
+            }
+        }
+
+        // Directories without a metadata file only have an ID
+        if kind == "directory" {
+            if let (Some(path), Some(id)) = (path, id) {
+                out_cache.insert_directory(path, id);
+            }
+            continue;
+        }
+
+        let (Some(path), Some(modified), Some(size), Some(hash), Some(id)) = (path, modified, size, hash, id) else {
+            continue;
+        };
+
+        let stamp = FileStamp::new(modified, size, hash);
+
+        match kind {
+            "note" => {
+                if let (Some(created_at), Some(title), Some(file_type)) = (created_at, title, file_type) {
+                    let mut data = NoteData::new();
+                    data.id = Some(id);
+                    data.title = Some(title);
+                    data.file_type = Some(file_type);
+                    data.tags = tags;
+                    data.properties = properties;
+                    data.line_ending = line_ending;
+                    data.has_byte_order_mark = has_byte_order_mark;
+
+                    out_cache.insert_note(path, CachedNote::new(stamp, created_at, data, indexed_body));
+                }
+            }
+            "folder" => {
+                if let Some(display_name) = display_name {
+                    let mut data = FolderData::new();
+                    data.id = Some(id);
+                    data.display_name = Some(display_name);
+                    data.properties = properties;
+
+                    out_cache.insert_folder(path, CachedFolder::new(stamp, data));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    return out_cache;
+}
+
+/// Splits off the next line, without its newline.
+fn next_line<'a>(rest: &mut &'a str) -> Option<&'a str> {
+    if rest.is_empty() {
+        return None;
+    }
+
+    let (line, remaining) = rest.split_once('\n').unwrap_or((rest, ""));
+    *rest = remaining;
+
+    return Some(line);
+}
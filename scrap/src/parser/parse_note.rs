@@ -1,21 +1,116 @@
-use crate::model::{Note, NoteData};
+use crate::model::{LineEnding, Note, NoteData};
+use crate::text::extract_list::extract_list;
 use crate::text::extract_quoted::extract_quoted;
+use crate::text::property_value::property_value;
 
 use std::str::FromStr;
 use uuid::Uuid;
 
-pub fn parse_note(input: String) -> NoteData {
+/// Splits the note into its front matter and body, a note without front matter is all body.
+/// Returns `None` if the front matter isn't closed or has an ID that can't be read.
+pub fn parse_note(input: String) -> Option<NoteData> {
     let mut out_data = NoteData::new();
 
+    // Saving writes the byte order mark back, and the front matter with the same line breaks as the rest of the file
+    out_data.has_byte_order_mark = input.starts_with('\u{feff}');
+    out_data.line_ending = LineEnding::detect(&input);
+
+    let (metadata_part, body_part) = split_note(&input)?;
+
+    // Index of the last unknown key, the lines continuing its value are added to it
+    let mut last_property = None;
+    // Set after an empty `tags:`, the lines below it are its YAML list items, e.g. `  - project/scrap`
+    let mut is_in_tag_list = false;
+
+    for line in metadata_part.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed == "---" {
+            continue;
+        }
+
+        // Indented lines and list items continue the value above them, like a YAML list
+        if line.starts_with(char::is_whitespace) || line.starts_with('-') || !trimmed.contains(':') {
+            if is_in_tag_list && let Some(item) = trimmed.strip_prefix('-') {
+                out_data.tags.push(property_value(item));
+            } else if let Some(index) = last_property {
+                let (_, value): &mut (String, String) = &mut out_data.properties[index];
+                value.push('\n');
+                value.push_str(line.trim_end());
+            }
+            continue;
+        }
+
+        last_property = None;
+        is_in_tag_list = false;
+
+        if let Some((key, value)) = trimmed.split_once(':') {
+            // Keys from other tools are kept as written, so saving the note doesn't drop them
+            if !matches!(key.trim(), "id" | "title" | "type" | "tags") {
+                out_data.properties.push((key.trim().to_string(), value.to_string()));
+                last_property = Some(out_data.properties.len() - 1);
+                continue;
+            }
+
+            // Lists have to be checked before single values, their items may be quoted as well
+            if key.trim() == "tags" {
+                if let Some(tags) = extract_list(value) {
+                    out_data.tags = tags;
+                } else if value.trim().is_empty() {
+                    is_in_tag_list = true;
+                } else {
+                    // Tags that can't be read are kept as written, so saving the note doesn't drop them
+                    out_data.properties.push((key.trim().to_string(), value.to_string()));
+                    last_property = Some(out_data.properties.len() - 1);
+                }
+                continue;
+            }
+
+            let extracted_value = match extract_quoted(value) {
+                Some(extracted_value) => extracted_value,
+                // Values written by hand may be unquoted, they're read like the values of unknown keys
+                None if !value.trim().is_empty() => property_value(value),
+                None => {
+                    // An unreadable ID would be replaced by a new one, so the note is rejected instead
+                    if key.trim() == "id" {
+                        return None;
+                    }
+
+                    // No value found, invalid
+                    continue;
+                }
+            };
+
+            match key.trim() {
+                "id" => out_data.id = Some(Uuid::from_str(&extracted_value).ok()?),
+                "title" => out_data.title = Some(extracted_value),
+                "type" => out_data.file_type = Some(extracted_value),
+                _ => {}
+            }
+        }
+    }
+
+    out_data.body = body_part.to_string();
+
+    return Some(out_data);
+}
+
+/// Splits the note into its front matter, including the delimiters, and its body without reading either.
+/// A note without front matter is all body, returns `None` if the front matter isn't closed.
+pub fn split_note(input: &str) -> Option<(&str, &str)> {
+    // A byte order mark would hide the opening delimiter
+    let content = input.strip_prefix('\u{feff}').unwrap_or(input);
+
     let mut opening_delimiter_found = false;
     let mut closing_delimiter_found = false;
 
     let mut metadata_end_byte_offset = 0;
     let mut current_byte_offset = 0;
 
-    for (index, line) in input.lines().enumerate() {
+    // Lines keep their line breaks so the offsets are exact, whether they're `\n`, `\r\n` or missing
+    for (index, line) in content.split_inclusive('\n').enumerate() {
         let trimmed = line.trim();
-        current_byte_offset += line.len() + 1;
+        current_byte_offset += line.len();
 
         // Skip opening delimiter
         if index == 0 && trimmed == "---" {
@@ -32,42 +127,17 @@ pub fn parse_note(input: String) -> NoteData {
         }
     }
 
-    // No opening or closing delimiter not found, return the whole input as body
-    if !opening_delimiter_found || !closing_delimiter_found {
-        out_data.body = input;
-        return out_data;
+    // No opening delimiter found, the whole input is body
+    if !opening_delimiter_found {
+        return Some(("", content));
     }
 
-    let metadata_part = &input[..metadata_end_byte_offset];
-    let body_part = &input[metadata_end_byte_offset..];
-
-    for line in metadata_part.lines() {
-        let trimmed = line.trim();
-
-        if trimmed.is_empty() || trimmed == "---" {
-            continue;
-        }
-
-        if let Some((key, value)) = trimmed.split_once(':') {
-            let Some(extracted_value) = extract_quoted(value) else {
-                // No value found inside qouted, invalid
-                continue;
-            };
-
-            match key.trim() {
-                "id" => {
-                    if let Ok(id) = Uuid::from_str(extracted_value) {
-                        out_data.id = Some(id);
-                    }
-                }
-                "title" => out_data.title = Some(extracted_value.to_string()),
-                "type" => out_data.file_type = Some(extracted_value.to_string()),
-                _ => {}
-            }
-        }
+    if !closing_delimiter_found {
+        return None;
     }
 
-    out_data.body = body_part.to_string();
-
-    return out_data;
+    return Some((
+        &content[..metadata_end_byte_offset],
+        &content[metadata_end_byte_offset..],
+    ));
 }
@@ -0,0 +1,318 @@
+use crate::api::QueryError;
+use crate::query::{Clause, Query, Term, TimeRange};
+use crate::text::normalize_tag::normalize_tag;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Names that start a `field:value` clause, other words with a `:` are matched as text
+const FIELDS: &[&str] = &["type", "in", "tag", "title", "created", "modified"];
+
+/// Parses a query such as `type:markdown in:work title:"weekly" -archived created:>2026-01-01`.
+///
+/// Clauses are separated by whitespace and all of them have to match.
+/// Bare words and quoted phrases are matched as text, `field:value` pairs filter by metadata,
+/// and a leading `-` negates a clause. Words like `10:30` or `http://x` are bare words.
+pub fn parse_query(input: &str) -> Result<Query, QueryError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut clauses = Vec::new();
+
+    let mut i = 0;
+
+    loop {
+        // Skip whitespace between clauses
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+
+        if i >= chars.len() {
+            break;
+        }
+
+        let clause_start = i;
+
+        let negated = chars[i] == '-';
+        if negated {
+            i += 1;
+
+            if i >= chars.len() || chars[i].is_whitespace() {
+                return Err(QueryError::MissingValue(clause_start));
+            }
+        }
+
+        // Quoted phrase
+        if chars[i] == '"' {
+            let (phrase, next) = read_quoted(&chars, i)?;
+            i = next;
+
+            clauses.push(Clause {
+                negated,
+                term: Term::Text(phrase),
+            });
+            continue;
+        }
+
+        let key_start = i;
+        while i < chars.len() && !chars[i].is_whitespace() && chars[i] != ':' && chars[i] != '"' {
+            i += 1;
+        }
+        let key: String = chars[key_start..i].iter().collect();
+
+        let is_field = i < chars.len() && chars[i] == ':' && FIELDS.contains(&key.as_str());
+
+        // Bare word
+        if !is_field {
+            while i < chars.len() && !chars[i].is_whitespace() {
+                i += 1;
+            }
+
+            clauses.push(Clause {
+                negated,
+                term: Term::Text(chars[key_start..i].iter().collect()),
+            });
+            continue;
+        }
+
+        // Skip the ':'
+        i += 1;
+
+        let value_start = i;
+        let value = if i < chars.len() && chars[i] == '"' {
+            let (value, next) = read_quoted(&chars, i)?;
+            i = next;
+            value
+        } else {
+            while i < chars.len() && !chars[i].is_whitespace() {
+                i += 1;
+            }
+            chars[value_start..i].iter().collect()
+        };
+
+        if value.is_empty() {
+            return Err(QueryError::MissingValue(value_start));
+        }
+
+        // Only names in `FIELDS` get here, the last one is `modified`
+        let term = match key.as_str() {
+            "type" => Term::FileType(value.to_ascii_lowercase()),
+            "in" => Term::Folder(value),
+            "tag" => Term::Tag(normalize_tag(&value).unwrap_or(value.to_lowercase())),
+            "title" => Term::Title(value),
+            "created" => Term::Created(parse_time_range(&value, value_start)?),
+            _ => Term::Modified(parse_time_range(&value, value_start)?),
+        };
+
+        clauses.push(Clause { negated, term });
+    }
+
+    return Ok(Query { clauses });
+}
+
+/// Reads a `"` quoted string starting at `start`, unescaping `\"` and `\\`.
+/// Returns the string and the index right after the closing quote.
+fn read_quoted(chars: &[char], start: usize) -> Result<(String, usize), QueryError> {
+    let mut out = String::new();
+    let mut i = start + 1;
+
+    while i < chars.len() {
+        match chars[i] {
+            '"' => return Ok((out, i + 1)),
+            '\\' if i + 1 < chars.len() => {
+                out.push(chars[i + 1]);
+                i += 2;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    return Err(QueryError::UnterminatedQuote(start));
+}
+
+/// Parses a date with an optional comparison, e.g. `2026-01-01`, `>2026-01-01` or `<=2026-01-31`.
+/// Dates are whole days in UTC, so `>2026-01-01` starts on January 2nd.
+fn parse_time_range(value: &str, position: usize) -> Result<TimeRange, QueryError> {
+    let (operator, date) = [">=", "<=", ">", "<", "="]
+        .iter()
+        .find_map(|op| value.strip_prefix(op).map(|date| (*op, date)))
+        .unwrap_or(("=", value));
+
+    let day_start = parse_date(date).ok_or(QueryError::InvalidDate(position, value.to_string()))?;
+    let day_end = day_start + SECONDS_PER_DAY;
+
+    let range = match operator {
+        ">" => TimeRange {
+            start: Some(day_end),
+            end: None,
+        },
+        ">=" => TimeRange {
+            start: Some(day_start),
+            end: None,
+        },
+        "<" => TimeRange {
+            start: None,
+            end: Some(day_start),
+        },
+        "<=" => TimeRange {
+            start: None,
+            end: Some(day_end),
+        },
+        _ => TimeRange {
+            start: Some(day_start),
+            end: Some(day_end),
+        },
+    };
+
+    return Ok(range);
+}
+
+/// Parses a `YYYY-MM-DD` date into the Unix timestamp of its start in UTC.
+fn parse_date(date: &str) -> Option<u64> {
+    let mut parts = date.split('-');
+
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+
+    if parts.next().is_some() || !(1970..=9999).contains(&year) || !(1..=12).contains(&month) {
+        return None;
+    }
+
+    if day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    // Days since the Unix epoch, from Howard Hinnant's `days_from_civil`
+    let shifted_year = if month <= 2 { year - 1 } else { year };
+    let era = shifted_year.div_euclid(400);
+    let year_of_era = shifted_year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+
+    return Some(days as u64 * SECONDS_PER_DAY);
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    let is_leap_year = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+
+    return match month {
+        2 if is_leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SECONDS_PER_DAY, parse_date, parse_query};
+    use crate::api::QueryError;
+    use crate::query::{Term, TimeRange};
+
+    fn created_range(input: &str) -> TimeRange {
+        let mut query = parse_query(input).unwrap();
+
+        return match query.clauses.remove(0).term {
+            Term::Created(range) => range,
+            _ => panic!("expected a created clause in {:?}", input),
+        };
+    }
+
+    #[test]
+    fn parses_words_phrases_fields_and_negation() {
+        let query = parse_query(r#"budget "weekly meeting" type:Markdown -tag:Work/Done title:"a \"b\"""#).unwrap();
+        let clauses = &query.clauses;
+
+        assert_eq!(clauses.len(), 5);
+        assert!(matches!(&clauses[0].term, Term::Text(text) if text == "budget"));
+        assert!(matches!(&clauses[1].term, Term::Text(text) if text == "weekly meeting"));
+        assert!(matches!(&clauses[2].term, Term::FileType(file_type) if file_type == "markdown"));
+        assert!(clauses[3].negated);
+        assert!(matches!(&clauses[3].term, Term::Tag(tag) if tag == "work/done"));
+        assert!(matches!(&clauses[4].term, Term::Title(title) if title == "a \"b\""));
+    }
+
+    #[test]
+    fn grammar_errors_report_their_position() {
+        assert!(matches!(
+            parse_query(r#"notes "open"#),
+            Err(QueryError::UnterminatedQuote(6))
+        ));
+        assert!(matches!(parse_query("a - b"), Err(QueryError::MissingValue(2))));
+        assert!(matches!(parse_query("type:"), Err(QueryError::MissingValue(5))));
+        assert!(matches!(
+            parse_query("created:2026-13-01"),
+            Err(QueryError::InvalidDate(8, _))
+        ));
+    }
+
+    #[test]
+    fn words_with_unknown_fields_are_text() {
+        let query = parse_query("10:30 http://x.org/a:b color:red -:x").unwrap();
+        let texts: Vec<&str> = query
+            .clauses
+            .iter()
+            .map(|clause| match &clause.term {
+                Term::Text(text) => text.as_str(),
+                _ => panic!("expected only text clauses"),
+            })
+            .collect();
+
+        assert_eq!(texts, ["10:30", "http://x.org/a:b", "color:red", ":x"]);
+        assert!(query.clauses[3].negated);
+    }
+
+    #[test]
+    fn date_filters_cover_whole_days() {
+        let day = parse_date("2026-01-01").unwrap();
+
+        let on = created_range("created:2026-01-01");
+        assert_eq!((on.start, on.end), (Some(day), Some(day + SECONDS_PER_DAY)));
+
+        let after = created_range("created:>2026-01-01");
+        assert_eq!((after.start, after.end), (Some(day + SECONDS_PER_DAY), None));
+
+        let from = created_range("created:>=2026-01-01");
+        assert_eq!((from.start, from.end), (Some(day), None));
+
+        let before = created_range("created:<2026-01-01");
+        assert_eq!((before.start, before.end), (None, Some(day)));
+
+        let until = created_range("created:<=2026-01-01");
+        assert_eq!((until.start, until.end), (None, Some(day + SECONDS_PER_DAY)));
+    }
+
+    #[test]
+    fn dates_convert_to_unix_timestamps() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2000-03-01"), Some(951_868_800));
+        assert_eq!(parse_date("2026-01-01"), Some(1_767_225_600));
+    }
+
+    #[test]
+    fn days_are_checked_against_the_month_length() {
+        assert_eq!(parse_date("2026-02-31"), None);
+        assert_eq!(parse_date("2026-04-31"), None);
+        assert_eq!(parse_date("2026-02-29"), None);
+        assert!(parse_date("2024-02-29").is_some());
+        assert!(parse_date("2000-02-29").is_some());
+        assert_eq!(parse_date("2100-02-29"), None);
+        assert!(parse_date("2026-12-31").is_some());
+        assert_eq!(parse_date("2026-01-00"), None);
+
+        assert!(matches!(
+            parse_query("modified:<2026-02-30"),
+            Err(QueryError::InvalidDate(9, _))
+        ));
+    }
+
+    #[test]
+    fn malformed_dates_are_rejected() {
+        assert_eq!(parse_date("2026-01"), None);
+        assert_eq!(parse_date("2026-01-01-01"), None);
+        assert_eq!(parse_date("1969-12-31"), None);
+        assert_eq!(parse_date("yesterday"), None);
+    }
+}
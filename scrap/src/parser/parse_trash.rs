@@ -0,0 +1,87 @@
+use crate::model::TrashEntry;
+use crate::text::extract_quoted::extract_quoted;
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Parses the trash manifest into its entries.
+/// Each entry is a `---` delimited block, entries missing a required key are skipped.
+pub fn parse_trash(input: String) -> Vec<TrashEntry> {
+    let mut out_entries = Vec::new();
+
+    let mut inside_entry = false;
+
+    let mut id = None;
+    let mut name = None;
+    let mut parent_id = None;
+    let mut original_path = None;
+    let mut trash_path = None;
+    let mut deleted_at = None;
+    // Entries without a kind were written before folders could be trashed
+    let mut is_folder = false;
+
+    for line in input.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if trimmed == "---" {
+            // Closing delimiter, collect the entry if it's complete
+            if inside_entry {
+                if let (
+                    Some(id),
+                    Some(name),
+                    Some(parent_id),
+                    Some(original_path),
+                    Some(trash_path),
+                    Some(deleted_at),
+                ) = (
+                    id.take(),
+                    name.take(),
+                    parent_id.take(),
+                    original_path.take(),
+                    trash_path.take(),
+                    deleted_at.take(),
+                ) {
+                    out_entries.push(TrashEntry::new(
+                        id,
+                        name,
+                        parent_id,
+                        original_path,
+                        trash_path,
+                        deleted_at,
+                        is_folder,
+                    ));
+                }
+
+                is_folder = false;
+            }
+
+            inside_entry = !inside_entry;
+            continue;
+        }
+
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let Some(extracted_value) = extract_quoted(value) else {
+                // No value found inside qouted, invalid
+                continue;
+            };
+
+            match key.trim() {
+                "id" => id = Uuid::from_str(&extracted_value).ok(),
+                "kind" => is_folder = extracted_value == "folder",
+                "name" => name = Some(extracted_value),
+                "parent-id" => parent_id = Uuid::from_str(&extracted_value).ok(),
+                "original-path" => original_path = Some(PathBuf::from(extracted_value)),
+                "trash-path" => trash_path = Some(PathBuf::from(extracted_value)),
+                "deleted-at" => deleted_at = extracted_value.parse::<u64>().ok(),
+                _ => {}
+            }
+        }
+    }
+
+    return out_entries;
+}
@@ -0,0 +1,27 @@
+use crate::model::WorkspaceSettings;
+use crate::text::extract_quoted::extract_quoted;
+
+pub fn parse_workspace_settings(input: String) -> WorkspaceSettings {
+    let mut out_settings = WorkspaceSettings::new();
+
+    for line in input.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed == "---" {
+            continue;
+        }
+
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let Some(extracted_value) = extract_quoted(value) else {
+                // No value found inside qouted, invalid
+                continue;
+            };
+
+            if key.trim() == "trash-retention-days" {
+                out_settings.trash_retention_days = extracted_value.parse::<u64>().ok();
+            }
+        }
+    }
+
+    return out_settings;
+}
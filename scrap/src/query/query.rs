@@ -0,0 +1,42 @@
+/// A parsed note query, every clause has to match for a note to be selected.
+pub struct Query {
+    pub clauses: Vec<Clause>,
+}
+
+pub struct Clause {
+    /// Set by a leading `-`, selects notes the term does not match
+    pub negated: bool,
+    pub term: Term,
+}
+
+pub enum Term {
+    /// Bare word or quoted phrase, matched against note titles and bodies
+    Text(String),
+    /// `type:<file type>`
+    FileType(String),
+    /// `in:<folder name or ID>`, matches notes inside the folder or any of its subfolders
+    Folder(String),
+    /// `tag:<tag>`, matches notes with the tag or any tag nested below it
+    Tag(String),
+    /// `title:<text>`, matches titles containing the text, ignoring case
+    Title(String),
+    /// `created:<date>`, e.g. `created:>2026-01-01`
+    Created(TimeRange),
+    /// `modified:<date>`, e.g. `modified:<=2026-01-31`
+    Modified(TimeRange),
+}
+
+/// Half-open range of Unix timestamps in seconds, unbounded on a missing side.
+pub struct TimeRange {
+    pub start: Option<u64>,
+    pub end: Option<u64>,
+}
+
+impl TimeRange {
+    pub fn contains(self: &Self, timestamp: u64) -> bool {
+        let after_start = self.start.is_none_or(|start| timestamp >= start);
+        let before_end = self.end.is_none_or(|end| timestamp < end);
+
+        return after_start && before_end;
+    }
+}
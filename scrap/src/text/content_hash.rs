@@ -0,0 +1,15 @@
+// 64-bit FNV-1a parameters
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Hashes file content with FNV-1a, which unlike `DefaultHasher` is stable across Rust versions.
+pub fn content_hash(content: &str) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+
+    for byte in content.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    return hash;
+}
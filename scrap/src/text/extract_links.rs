@@ -0,0 +1,28 @@
+/// Extracts the targets of wiki-style links such as `[[Note Title]]`, `[[uuid]]` or `[[Note Title|label]]`.
+/// Links can't span multiple lines, empty targets are skipped.
+pub fn extract_links(body: &str) -> Vec<&str> {
+    let mut targets = Vec::new();
+
+    for line in body.lines() {
+        let mut rest = line;
+
+        while let Some(open) = rest.find("[[") {
+            let inner = &rest[open + 2..];
+
+            let Some(close) = inner.find("]]") else {
+                break;
+            };
+
+            // Drop the display label after `|`
+            let target = inner[..close].split('|').next().unwrap_or("").trim();
+
+            if !target.is_empty() {
+                targets.push(target);
+            }
+
+            rest = &inner[close + 2..];
+        }
+    }
+
+    return targets;
+}
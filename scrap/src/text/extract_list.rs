@@ -0,0 +1,77 @@
+use crate::text::extract_quoted::read_quoted;
+
+/// Extracts the items of a YAML flow list such as `["a", "b"]` or `[a, 'b']`.
+/// Double quoted items are unescaped like `escape` writes them, bare items are trimmed.
+/// Returns `None` when the value is not wrapped in brackets or an item can't be read.
+pub fn extract_list(s: &str) -> Option<Vec<String>> {
+    let inner = s.trim().strip_prefix('[')?.strip_suffix(']')?;
+
+    let mut items = Vec::new();
+    let mut rest = inner.trim_start();
+
+    while !rest.is_empty() {
+        let (item, remaining) = if rest.starts_with('"') {
+            read_quoted(rest)?
+        } else if rest.starts_with('\'') {
+            read_single_quoted(rest)?
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            (rest[..end].trim_end().to_string(), &rest[end..])
+        };
+
+        if !item.is_empty() {
+            items.push(item);
+        }
+
+        // Items are separated by commas, anything else after an item is not a list
+        rest = remaining.trim_start();
+        if !rest.is_empty() {
+            rest = rest.strip_prefix(',')?.trim_start();
+        }
+    }
+
+    return Some(items);
+}
+
+/// Reads the YAML single quoted string at the start of `s`, where `''` stands for a quote.
+/// Returns the value and whatever follows the closing quote.
+fn read_single_quoted(s: &str) -> Option<(String, &str)> {
+    let mut inner = s.strip_prefix('\'')?;
+    let mut out = String::new();
+
+    loop {
+        let end = inner.find('\'')?;
+        out.push_str(&inner[..end]);
+
+        if inner[end + 1..].starts_with('\'') {
+            out.push('\'');
+            inner = &inner[end + 2..];
+        } else {
+            return Some((out, &inner[end + 1..]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_list;
+
+    #[test]
+    fn reads_quoted_and_bare_items() {
+        assert_eq!(extract_list(r#"["a", "b \"c\""]"#).unwrap(), ["a", "b \"c\""]);
+        assert_eq!(extract_list(" [work, personal] ").unwrap(), ["work", "personal"]);
+        assert_eq!(
+            extract_list("[a, 'b, c', 'it''s', \"d\",]").unwrap(),
+            ["a", "b, c", "it's", "d"]
+        );
+        assert!(extract_list("[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn values_that_are_not_lists_are_rejected() {
+        assert_eq!(extract_list("work"), None);
+        assert_eq!(extract_list("[work"), None);
+        assert_eq!(extract_list(r#"["a" "b"]"#), None);
+        assert_eq!(extract_list(r#"["unterminated]"#), None);
+    }
+}
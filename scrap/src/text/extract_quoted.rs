@@ -1,9 +1,48 @@
-pub fn extract_quoted(s: &str) -> Option<&str> {
+use crate::text::escape::unescape;
+
+/// Extracts the quoted value of a front matter line, reversing the escaping done by `escape`.
+/// Values written before quotes were escaped are read from the first to the last quote.
+///
+/// Old values can't be told apart from escaped ones, so the escape sequences `\\`, `\"`, `\n`,
+/// `\r` and `\t` in them are unescaped as well, e.g. a title written as `a\nb` becomes two lines.
+/// Any other backslash, like in `C:\Users`, is kept as it is.
+pub fn extract_quoted(s: &str) -> Option<String> {
+    let trimmed = s.trim();
+
+    if let Some((value, rest)) = read_quoted(trimmed)
+        && rest.trim().is_empty()
+    {
+        return Some(value);
+    }
+
     let start = s.find('"')?;
     let end = s.rfind('"')?;
     if start < end {
-        Some(&s[start + 1..end])
+        Some(s[start + 1..end].to_string())
     } else {
         None
     }
 }
+
+/// Reads the escaped string at the start of `s`, which has to begin with a quote.
+/// Returns the unescaped value and whatever follows the closing quote.
+pub fn read_quoted(s: &str) -> Option<(String, &str)> {
+    let inner = s.strip_prefix('"')?;
+
+    let mut is_escaped = false;
+    for (index, c) in inner.char_indices() {
+        if is_escaped {
+            is_escaped = false;
+            continue;
+        }
+
+        match c {
+            '\\' => is_escaped = true,
+            '"' => return Some((unescape(&inner[..index]), &inner[index + 1..])),
+            _ => {}
+        }
+    }
+
+    // Unterminated value
+    return None;
+}
@@ -0,0 +1,180 @@
+/// Lowercases the text and collapses every run of whitespace into a single space.
+pub fn normalize(input: &str) -> String {
+    return input
+        .split_whitespace()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<String>>()
+        .join(" ");
+}
+
+/// Scores how well a normalized title matches a normalized query, from 0 to 1.
+///
+/// Exact matches rank first, then prefix, word prefix and substring matches,
+/// then titles containing the query as a subsequence (e.g. "wkmt" in "weekly meeting"),
+/// and finally titles within a small edit distance to catch typos.
+/// Returns `None` if the title doesn't match at all.
+pub fn fuzzy_match(query: &str, title: &str) -> Option<f32> {
+    if query.is_empty() {
+        return None;
+    }
+
+    let query_len = query.chars().count() as f32;
+    let title_len = title.chars().count() as f32;
+    // Shorter titles are closer to the query within the same kind of match
+    let coverage = query_len / title_len.max(query_len);
+
+    if query == title {
+        return Some(1.0);
+    }
+
+    if title.starts_with(query) {
+        return Some(0.8 + 0.1 * coverage);
+    }
+
+    if title.split(' ').any(|word| word.starts_with(query)) {
+        return Some(0.7 + 0.1 * coverage);
+    }
+
+    if title.contains(query) {
+        return Some(0.6 + 0.1 * coverage);
+    }
+
+    if let Some(span) = subsequence_span(query, title) {
+        return Some(0.3 + 0.2 * (query_len / span as f32));
+    }
+
+    // Allow one typo for every four characters, so queries shorter than that
+    // don't match unrelated short words like "cat" matching "car" or "hat"
+    let max_distance = (query_len / 4.0) as usize;
+
+    if max_distance == 0 {
+        return None;
+    }
+
+    let distance = title
+        .split(' ')
+        .map(|word| edit_distance(query, word))
+        .chain(std::iter::once(edit_distance(query, title)))
+        .min()
+        .unwrap_or(usize::MAX);
+
+    if distance <= max_distance {
+        return Some(0.1 + 0.2 * (1.0 - distance as f32 / query_len));
+    }
+
+    return None;
+}
+
+/// Returns the length of the shortest window of `text` containing every char of `query` in order.
+fn subsequence_span(query: &str, text: &str) -> Option<usize> {
+    let query: Vec<char> = query.chars().filter(|c| *c != ' ').collect();
+    let text: Vec<char> = text.chars().collect();
+
+    if query.is_empty() {
+        return None;
+    }
+
+    let mut best_span: Option<usize> = None;
+
+    for start in 0..text.len() {
+        if text[start] != query[0] {
+            continue;
+        }
+
+        let mut query_index = 0;
+        for (offset, c) in text[start..].iter().enumerate() {
+            if *c == query[query_index] {
+                query_index += 1;
+
+                if query_index == query.len() {
+                    let span = offset + 1;
+                    best_span = Some(best_span.map_or(span, |best| best.min(span)));
+                    break;
+                }
+            }
+        }
+    }
+
+    return best_span;
+}
+
+/// Levenshtein distance between two strings, counted in chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    let mut current_row = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current_row[0] = i;
+
+        for j in 1..=b.len() {
+            let substitution_cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            current_row[j] = (previous_row[j] + 1)
+                .min(current_row[j - 1] + 1)
+                .min(previous_row[j - 1] + substitution_cost);
+        }
+
+        std::mem::swap(&mut previous_row, &mut current_row);
+    }
+
+    return previous_row[b.len()];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fuzzy_match, normalize};
+
+    #[test]
+    fn normalize_lowercases_and_collapses_whitespace() {
+        assert_eq!(normalize("  Weekly   Meeting\tNotes "), "weekly meeting notes");
+    }
+
+    #[test]
+    fn match_kinds_rank_in_order() {
+        let exact = fuzzy_match("meeting", "meeting").unwrap();
+        let prefix = fuzzy_match("meet", "meeting notes").unwrap();
+        let word_prefix = fuzzy_match("meet", "weekly meeting").unwrap();
+        let substring = fuzzy_match("eeting", "weekly meeting").unwrap();
+        let subsequence = fuzzy_match("wkmt", "weekly meeting").unwrap();
+        let typo = fuzzy_match("meetimg", "meeting").unwrap();
+
+        assert_eq!(exact, 1.0);
+        assert!(exact > prefix);
+        assert!(prefix > word_prefix);
+        assert!(word_prefix > substring);
+        assert!(substring > subsequence);
+        assert!(subsequence > typo);
+    }
+
+    #[test]
+    fn shorter_titles_rank_first_within_a_kind() {
+        assert!(fuzzy_match("meet", "meeting").unwrap() > fuzzy_match("meet", "meeting notes").unwrap());
+    }
+
+    #[test]
+    fn short_queries_need_an_exact_substring_or_subsequence() {
+        assert_eq!(fuzzy_match("cat", "car"), None);
+        assert_eq!(fuzzy_match("ab", "xy"), None);
+        assert_eq!(fuzzy_match("a", "b"), None);
+        assert!(fuzzy_match("cat", "concat").is_some());
+    }
+
+    #[test]
+    fn typos_allowed_grow_with_query_length() {
+        // One typo from four characters
+        assert!(fuzzy_match("rekipe", "recipe").is_some());
+        assert_eq!(fuzzy_match("rekype", "recipe"), None);
+
+        // Two typos from eight characters
+        assert!(fuzzy_match("grocerxez", "groceries").is_some());
+        assert_eq!(fuzzy_match("grxcerxez", "groceries"), None);
+    }
+
+    #[test]
+    fn empty_query_matches_nothing() {
+        assert_eq!(fuzzy_match("", "anything"), None);
+    }
+}
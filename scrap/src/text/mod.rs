@@ -1,3 +1,12 @@
+pub mod content_hash;
+pub mod escape;
+pub mod extract_links;
+pub mod extract_list;
 pub mod extract_quoted;
+pub mod fuzzy_match;
+pub mod normalize_tag;
+pub mod property_value;
 pub mod sanitize_name;
 pub mod slugify;
+pub mod snippet;
+pub mod tokenize;
@@ -0,0 +1,28 @@
+/// Normalizes a tag such as `#Project / Scrap` into `project/scrap`.
+/// Nested tags are separated by `/`, returns `None` if the tag or any of its segments is empty
+/// or contains a character that can't be stored in front matter.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim();
+    let tag = tag.strip_prefix('#').unwrap_or(tag);
+
+    let mut segments = Vec::new();
+
+    for segment in tag.split('/') {
+        let segment = segment.trim();
+
+        if segment.is_empty() || segment.contains(|c: char| matches!(c, '"' | '[' | ']' | ',') || c.is_control()) {
+            return None;
+        }
+
+        segments.push(segment.to_lowercase());
+    }
+
+    return Some(segments.join("/"));
+}
+
+/// Returns true if `tag` is `ancestor` itself or nested below it, e.g. `project/scrap` within `project`.
+pub fn is_tag_within(tag: &str, ancestor: &str) -> bool {
+    return tag
+        .strip_prefix(ancestor)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+}
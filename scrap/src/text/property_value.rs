@@ -0,0 +1,15 @@
+use crate::text::extract_quoted::read_quoted;
+
+/// Reads a front matter value as written by another tool.
+/// Quoted values are unescaped, anything else, like a YAML list, is returned trimmed as it is.
+pub fn property_value(raw_value: &str) -> String {
+    let trimmed = raw_value.trim();
+
+    if let Some((value, rest)) = read_quoted(trimmed)
+        && rest.trim().is_empty()
+    {
+        return value;
+    }
+
+    return trimmed.to_string();
+}
@@ -0,0 +1,42 @@
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+/// Returns a short excerpt of the text around the first occurrence of any of the query words,
+/// or the beginning of the text if none of them occurs literally.
+pub fn snippet(text: &str, query: &str) -> String {
+    let words: Vec<String> = query.split_whitespace().map(|w| w.to_lowercase()).collect();
+
+    for line in text.lines() {
+        let lowercase_line = line.to_lowercase();
+
+        let Some(byte_offset) = words.iter().filter_map(|w| lowercase_line.find(w.as_str())).min() else {
+            continue;
+        };
+
+        // Lowercasing keeps the number of chars for nearly all text, so map the match by char index
+        let match_index = lowercase_line[..byte_offset].chars().count();
+        let start = match_index.saturating_sub(SNIPPET_CONTEXT_CHARS);
+
+        return excerpt(line, start, SNIPPET_CONTEXT_CHARS * 2);
+    }
+
+    let first_line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or_default();
+    return excerpt(first_line, 0, SNIPPET_CONTEXT_CHARS * 2);
+}
+
+fn excerpt(line: &str, start: usize, length: usize) -> String {
+    let char_count = line.chars().count();
+
+    let mut out = String::new();
+
+    if start > 0 {
+        out.push('…');
+    }
+
+    out.extend(line.chars().skip(start).take(length));
+
+    if start + length < char_count {
+        out.push('…');
+    }
+
+    return out.trim().to_string();
+}
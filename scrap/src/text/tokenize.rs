@@ -0,0 +1,103 @@
+/// Splits text into lowercase search terms.
+///
+/// Words of space separated scripts become one term each. Runs of CJK characters
+/// are split into single characters and overlapping bigrams instead, since those scripts
+/// either don't separate words with spaces or attach particles to words (e.g. Korean "회의록을").
+/// The single characters let one character queries match inside longer runs.
+pub fn tokenize(input: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+
+    let mut word = String::new();
+    let mut cjk_run: Vec<char> = Vec::new();
+
+    for c in input.chars() {
+        if is_cjk(c) {
+            flush_word(&mut word, &mut terms);
+            cjk_run.push(c);
+        } else if c.is_alphanumeric() {
+            flush_cjk_run(&mut cjk_run, &mut terms);
+            word.extend(c.to_lowercase());
+        } else {
+            flush_word(&mut word, &mut terms);
+            flush_cjk_run(&mut cjk_run, &mut terms);
+        }
+    }
+
+    flush_word(&mut word, &mut terms);
+    flush_cjk_run(&mut cjk_run, &mut terms);
+
+    return terms;
+}
+
+fn flush_word(word: &mut String, terms: &mut Vec<String>) {
+    if !word.is_empty() {
+        terms.push(std::mem::take(word));
+    }
+}
+
+fn flush_cjk_run(run: &mut Vec<char>, terms: &mut Vec<String>) {
+    for (index, c) in run.iter().enumerate() {
+        terms.push(c.to_string());
+
+        if let Some(next) = run.get(index + 1) {
+            terms.push([*c, *next].iter().collect());
+        }
+    }
+
+    run.clear();
+}
+
+fn is_cjk(c: char) -> bool {
+    return matches!(c,
+        '\u{1100}'..='\u{11FF}'     // Hangul Jamo
+        | '\u{3040}'..='\u{30FF}'   // Hiragana and Katakana
+        | '\u{3130}'..='\u{318F}'   // Hangul Compatibility Jamo
+        | '\u{3400}'..='\u{4DBF}'   // CJK Unified Ideographs Extension A
+        | '\u{4E00}'..='\u{9FFF}'   // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}'   // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}'   // CJK Compatibility Ideographs
+        | '\u{FF66}'..='\u{FF9F}'   // Halfwidth Katakana
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tokenize;
+
+    #[test]
+    fn latin_words_are_lowercased_and_split_on_punctuation() {
+        assert_eq!(
+            tokenize("Hello, World! rust-2024"),
+            vec!["hello", "world", "rust", "2024"]
+        );
+    }
+
+    #[test]
+    fn cjk_runs_become_characters_and_overlapping_bigrams() {
+        assert_eq!(
+            tokenize("회의록을"),
+            vec!["회", "회의", "의", "의록", "록", "록을", "을"]
+        );
+        assert_eq!(tokenize("東京都"), vec!["東", "東京", "京", "京都", "都"]);
+    }
+
+    #[test]
+    fn single_cjk_character_is_kept_as_a_term() {
+        assert_eq!(tokenize("猫"), vec!["猫"]);
+    }
+
+    #[test]
+    fn mixed_cjk_and_latin_are_split_at_script_boundaries() {
+        assert_eq!(
+            tokenize("Rust로코딩 v2"),
+            vec!["rust", "로", "로코", "코", "코딩", "딩", "v2"]
+        );
+        assert_eq!(tokenize("API设计"), vec!["api", "设", "设计", "计"]);
+    }
+
+    #[test]
+    fn empty_and_punctuation_only_input_has_no_terms() {
+        assert!(tokenize("").is_empty());
+        assert!(tokenize(" -- !? ").is_empty());
+    }
+}
@@ -1,7 +1,17 @@
+use crate::api::{ScanReport, SkipReason, SkippedFile, UnsavedId};
 use crate::fs::fs_ops;
-use crate::model::{Folder, FolderData, FolderMetadata, Note, NoteData, NoteMetadata};
-use crate::parser::{parse_folder::parse_folder, parse_note::parse_note};
-use crate::text::{sanitize_name::sanitize_name, slugify::slugify};
+use crate::model::{
+    FileStamp, Folder, FolderData, FolderMetadata, IndexCache, IndexedBody, LineEnding, Note, NoteData, NoteMetadata,
+    TrashEntry, WorkspaceSettings,
+};
+use crate::parser::{
+    parse_folder::parse_folder,
+    parse_index_cache::parse_index_cache,
+    parse_note::{parse_note, split_note},
+    parse_trash::parse_trash,
+    parse_workspace_settings::parse_workspace_settings,
+};
+use crate::text::{content_hash::content_hash, sanitize_name::sanitize_name, slugify::slugify};
 use crate::workspace::{WorkspaceError, WorkspaceEvent};
 
 use std::collections::HashMap;
@@ -10,6 +20,7 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 use uuid::uuid;
 
@@ -22,15 +33,32 @@ const MAX_FILENAME_LEN: usize = FILENAME_LEN + FILENAME_SEPARATOR_LEN + UUID_LEN
 const MAX_FOLDERNAME_LEN: usize = FOLDERNAME_LEN + FILENAME_SEPARATOR_LEN + UUID_LEN;
 
 const METADATA_FILENAME: &str = "_metadata.txt";
+const SETTINGS_FILENAME: &str = "_workspace.txt";
+const TRASH_DIRNAME: &str = ".trash";
+const CACHE_DIRNAME: &str = ".cache";
+const TRASH_MANIFEST_FILENAME: &str = "_manifest.txt";
+const INDEX_CACHE_FILENAME: &str = "index.txt";
 const NOTE_FILE_EXTENSION: &str = "txt";
 
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+/// Files modified this close before a scan could change again without changing their
+/// modification time on coarse filesystems, so they are left out of the cache
+const RACY_WINDOW_NANOS: u64 = 2 * NANOS_PER_SECOND;
+
+/// Scanned notes and folders, the scan report and the IDs the index cache had for each path
+type ScannedWorkspace = (Vec<Note>, Vec<Folder>, ScanReport, HashMap<PathBuf, Uuid>);
+
 pub struct Workspace {
     workspace_dir: Option<PathBuf>,
+    settings: WorkspaceSettings,
 }
 
 impl Workspace {
     pub fn new() -> Self {
-        return Self { workspace_dir: None };
+        return Self {
+            workspace_dir: None,
+            settings: WorkspaceSettings::new(),
+        };
     }
 
     pub fn create_workspace(self: &mut Self, target: &Path) -> Result<(), WorkspaceError> {
@@ -39,27 +67,92 @@ impl Workspace {
         let workspace_dir = target.canonicalize().map_err(WorkspaceError::from_io)?;
 
         // Also create cache and trash folders
-        let tash_dir = workspace_dir.join(".trash");
+        let tash_dir = workspace_dir.join(TRASH_DIRNAME);
         fs_ops::create_dir(&workspace_dir, &tash_dir);
 
-        let cache_dir = workspace_dir.join(".cache");
+        let cache_dir = workspace_dir.join(CACHE_DIRNAME);
         fs_ops::create_dir(&workspace_dir, &cache_dir);
 
+        self.settings = Self::load_settings(&workspace_dir)?;
+
         self.workspace_dir = Some(workspace_dir);
         return Ok(());
     }
 
-    pub fn scan_workspace(self: &Self, workspace_id: Uuid) -> Result<(Vec<Note>, Vec<Folder>), WorkspaceError> {
+    pub fn get_settings(self: &Self) -> &WorkspaceSettings {
+        return &self.settings;
+    }
+
+    /// Replaces the workspace settings and writes them to the settings file.
+    pub fn save_settings(self: &mut Self, settings: WorkspaceSettings) -> Result<(), WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        let settings_path = Path::new(SETTINGS_FILENAME);
+
+        if !fs_ops::path_exists(workspace_dir, settings_path) {
+            fs_ops::create_file(workspace_dir, settings_path).map_err(WorkspaceError::from_io)?;
+        }
+
+        fs_ops::write_file(workspace_dir, settings_path, &settings.compose()).map_err(WorkspaceError::from_io)?;
+
+        self.settings = settings;
+        return Ok(());
+    }
+
+    /// Loads every note and folder in the workspace.
+    /// With lazy bodies, notes are returned without their body, only with the data the indexes need from it.
+    /// Also returns the IDs the index cache had for each path, before this scan.
+    pub fn scan_workspace(
+        self: &Self,
+        workspace_id: Uuid,
+        has_lazy_bodies: bool,
+    ) -> Result<ScannedWorkspace, WorkspaceError> {
         let workspace_dir = self
             .workspace_dir
             .as_ref()
             .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
 
-        return Self::scan_directory(workspace_dir, &PathBuf::new(), workspace_id);
+        let scan_started_at = Self::now_timestamp() * NANOS_PER_SECOND;
+
+        // Files that didn't change since the last scan are taken from the cache instead of being read
+        let mut cache = Self::load_index_cache(workspace_dir);
+        let cached_ids = cache.cached_ids();
+        let mut stamps = HashMap::new();
+
+        // Files that can't be loaded are skipped and reported instead of failing the scan
+        let mut report = ScanReport::new();
+
+        let (notes, folders) = Self::scan_directory(
+            workspace_dir,
+            &PathBuf::new(),
+            workspace_id,
+            has_lazy_bodies,
+            &mut cache,
+            &mut stamps,
+            &mut report,
+        )?;
+
+        stamps.retain(|_, stamp| stamp.modified + RACY_WINDOW_NANOS < scan_started_at);
+
+        // The cache only speeds up the next scan, failing to write it doesn't fail this one
+        if cache.is_outdated() {
+            let _ = Self::save_index_cache(workspace_dir, &IndexCache::compose(&notes, &folders, &stamps));
+        }
+
+        return Ok((notes, folders, report, cached_ids));
     }
 
     /// Creates a new note with embedded metadata and saves it to the workspace.
-    pub fn create_note(self: &Self, parent_dir: &Path, title: &str, file_type: &str) -> Result<Note, WorkspaceError> {
+    pub fn create_note(
+        self: &Self,
+        parent_dir: &Path,
+        title: &str,
+        file_type: &str,
+        parent_id: Uuid,
+    ) -> Result<Note, WorkspaceError> {
         let workspace_dir = self
             .workspace_dir
             .as_ref()
@@ -82,35 +175,139 @@ impl Workspace {
             return Err(WorkspaceError::from_io(err));
         }
 
-        return Ok(Note::new(file_path, metadata));
+        let mut note = Note::new(file_path, metadata, parent_id);
+
+        let now = Self::now_timestamp();
+        note.set_timestamps(now, now);
+
+        return Ok(note);
     }
 
     /// Saves note's content to the corresponding file in storage.
-    /// TODO: changing note's title should trigger file rename to be consistent
-    pub fn save_note(self: &Self, note: &Note) -> Result<WorkspaceEvent, WorkspaceError> {
+    /// Title changes go through `rename_note` so the file name stays consistent.
+    /// Notes without a loaded body only have their front matter rewritten.
+    pub fn save_note(self: &Self, note: &mut Note) -> Result<WorkspaceEvent, WorkspaceError> {
         let workspace_dir = self
             .workspace_dir
             .as_ref()
             .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
 
         let note_path = note.get_relative_path();
-        let content_to_save = note.compose();
 
-        fs_ops::write_file(workspace_dir, &note_path, &content_to_save).map_err(WorkspaceError::from_io)?;
+        match note.compose() {
+            Some(content_to_save) => {
+                fs_ops::write_file(workspace_dir, &note_path, &content_to_save).map_err(WorkspaceError::from_io)?;
+            }
+            None => Self::write_note_metadata(workspace_dir, note_path, note.get_metadata())?,
+        }
+
+        note.set_timestamps(note.get_created_at(), Self::now_timestamp());
 
         return Ok(WorkspaceEvent::NoteContentSaved);
     }
 
     /// Loads a note and it's metadata from the specified path.
-    pub fn load_note(self: &Self, file_path: &Path) -> Result<Note, WorkspaceError> {
+    pub fn load_note(self: &Self, file_path: &Path, parent_id: Uuid) -> Result<Note, WorkspaceError> {
         let workspace_dir = self
             .workspace_dir
             .as_ref()
             .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
 
-        let data = Self::load_note_data(workspace_dir, file_path)?;
+        return Self::load_note_file(workspace_dir, file_path, parent_id);
+    }
+
+    /// Renames the note's file to match the new title and rewrites its front matter.
+    /// The body on disk is kept as is, so unsaved changes held in memory are not written.
+    /// If rewriting the front matter fails, the file is moved back to its old name.
+    pub fn rename_note(self: &Self, note: &Note, new_title: &str) -> Result<PathBuf, WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        let current_path = note.get_relative_path();
+        let parent_dir = current_path.parent().ok_or(WorkspaceError::InvalidPath)?;
+        let new_path = parent_dir.join(Self::note_file_name(new_title, note.get_id()));
+
+        // Move the file only when the slug actually changes
+        if new_path != current_path {
+            if fs_ops::path_exists(workspace_dir, &new_path) {
+                return Err(WorkspaceError::NameCollision);
+            }
+
+            fs_ops::move_file(workspace_dir, current_path, &new_path).map_err(WorkspaceError::from_io)?;
+        }
+
+        // Keep everything else in the front matter, like tags, as it is
+        let mut metadata = note.get_metadata().clone();
+        metadata.set_title(new_title);
+
+        if let Err(err) = Self::write_note_metadata(workspace_dir, &new_path, &metadata) {
+            // Rollback the file to its old name
+            if new_path != current_path {
+                let _ = fs_ops::move_file(workspace_dir, &new_path, current_path);
+            }
+
+            return Err(err);
+        }
+
+        return Ok(new_path);
+    }
+
+    /// Gives the note a new ID by rewriting its front matter and renaming its file to match.
+    pub fn reassign_note_id(self: &Self, note: &Note, new_id: Uuid) -> Result<PathBuf, WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        let current_path = note.get_relative_path();
+        let parent_dir = current_path.parent().ok_or(WorkspaceError::InvalidPath)?;
+        let new_path = parent_dir.join(Self::note_file_name(note.get_title(), new_id));
+
+        if fs_ops::path_exists(workspace_dir, &new_path) {
+            return Err(WorkspaceError::NameCollision);
+        }
+
+        fs_ops::move_file(workspace_dir, current_path, &new_path).map_err(WorkspaceError::from_io)?;
+
+        let mut metadata = note.get_metadata().clone();
+        metadata.set_id(new_id);
+
+        if let Err(err) = Self::write_note_metadata(workspace_dir, &new_path, &metadata) {
+            // Rollback the file to its old name
+            let _ = fs_ops::move_file(workspace_dir, &new_path, current_path);
+
+            return Err(err);
+        }
+
+        return Ok(new_path);
+    }
+
+    /// Moves the note's file into the target folder directory, keeping its file name.
+    pub fn move_note(self: &Self, note: &Note, target_dir: &Path) -> Result<PathBuf, WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        // Notes can only be moved into regular folders
+        if Self::is_reserved_dir(target_dir) {
+            return Err(WorkspaceError::ReservedDirectory);
+        }
+
+        let current_path = note.get_relative_path();
+
+        let file_name = current_path.file_name().ok_or(WorkspaceError::InvalidPath)?;
+        let new_path = target_dir.join(file_name);
+
+        if fs_ops::path_exists(workspace_dir, &new_path) {
+            return Err(WorkspaceError::NameCollision);
+        }
+
+        fs_ops::move_file(workspace_dir, current_path, &new_path).map_err(WorkspaceError::from_io)?;
 
-        return Ok(Note::from_data(file_path.to_path_buf(), data));
+        return Ok(new_path);
     }
 
     pub fn move_note_to_trash(self: &Self, note: &mut Note) -> Result<(), WorkspaceError> {
@@ -119,21 +316,180 @@ impl Workspace {
             .as_ref()
             .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
 
-        let trash_dir = Path::new(".trash");
+        let trash_dir = Path::new(TRASH_DIRNAME);
         fs_ops::ensure_dir(workspace_dir, trash_dir).map_err(WorkspaceError::from_io)?;
 
         let current_path = note.get_relative_path();
 
-        let file_name = current_path.file_name().ok_or(WorkspaceError::InvalidPath)?;
-        let new_path = trash_dir.join(file_name);
+        // Never overwrite another trashed item or the manifest
+        let new_path = trash_dir.join(Self::trash_name(current_path, note.get_id())?);
+
+        if fs_ops::path_exists(workspace_dir, &new_path) {
+            return Err(WorkspaceError::NameCollision);
+        }
 
         fs_ops::move_file(workspace_dir, current_path, &new_path).map_err(WorkspaceError::from_io)?;
 
+        // Record where the note came from so it can be restored
+        let entry = TrashEntry::new(
+            note.get_id(),
+            note.get_title(),
+            note.get_parent_id(),
+            current_path.to_path_buf(),
+            new_path.clone(),
+            Self::now_timestamp(),
+            false,
+        );
+
+        if let Err(err) = Self::add_trash_entry(workspace_dir, entry) {
+            // Rollback the note out of trash
+            let _ = fs_ops::move_file(workspace_dir, &new_path, current_path);
+
+            return Err(err);
+        }
+
         note.mark_as_deleted();
 
         return Ok(());
     }
 
+    /// Lists every item recorded in the trash manifest.
+    pub fn list_trash(self: &Self) -> Result<Vec<TrashEntry>, WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        return Self::load_trash_entries(workspace_dir);
+    }
+
+    /// Moves a trashed note back into the target folder directory and removes it from the trash manifest.
+    pub fn restore_note(self: &Self, id: Uuid, target_dir: &Path, parent_id: Uuid) -> Result<Note, WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        let mut entries = Self::load_trash_entries(workspace_dir)?;
+
+        let position = entries
+            .iter()
+            .position(|e| e.get_id() == id && !e.is_folder())
+            .ok_or(WorkspaceError::NotFound)?;
+        let entry = entries.remove(position);
+
+        let trash_path = entry.get_trash_path();
+
+        // Restore under the original name, the trash name may carry the ID
+        let file_name = entry
+            .get_original_path()
+            .file_name()
+            .ok_or(WorkspaceError::InvalidPath)?;
+        let new_path = target_dir.join(file_name);
+
+        if fs_ops::path_exists(workspace_dir, &new_path) {
+            return Err(WorkspaceError::NameCollision);
+        }
+
+        fs_ops::move_file(workspace_dir, trash_path, &new_path).map_err(WorkspaceError::from_io)?;
+
+        let remaining_entries: Vec<&TrashEntry> = entries.iter().collect();
+        if let Err(err) = Self::save_trash_entries(workspace_dir, &remaining_entries) {
+            // Rollback the note into trash
+            let _ = fs_ops::move_file(workspace_dir, &new_path, trash_path);
+
+            return Err(err);
+        }
+
+        return Self::load_note_file(workspace_dir, &new_path, parent_id);
+    }
+
+    /// Permanently deletes trashed items that have been in the trash for at least `older_than`,
+    /// or every trashed item when no age is given.
+    /// Returns the deleted entries and the entries that failed to delete, which stay in the trash.
+    pub fn purge_trash(
+        self: &Self,
+        older_than: Option<Duration>,
+    ) -> Result<(Vec<TrashEntry>, Vec<TrashEntry>), WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        let cutoff = older_than.map(|age| Self::now_timestamp().saturating_sub(age.as_secs()));
+
+        let entries = Self::load_trash_entries(workspace_dir)?;
+
+        let mut kept_entries = Vec::new();
+        let mut deleted_entries = Vec::new();
+        let mut failed_entries = Vec::new();
+
+        for entry in entries {
+            let is_expired = cutoff.map_or(true, |cutoff| entry.get_deleted_at() <= cutoff);
+
+            if !is_expired {
+                kept_entries.push(entry);
+                continue;
+            }
+
+            let result = if entry.is_folder() {
+                fs_ops::delete_dir_all(workspace_dir, entry.get_trash_path())
+            } else {
+                fs_ops::delete_file(workspace_dir, entry.get_trash_path())
+            };
+
+            match result {
+                Ok(_) => deleted_entries.push(entry),
+                // Already gone from disk, only the record is left
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => deleted_entries.push(entry),
+                Err(_) => failed_entries.push(entry),
+            }
+        }
+
+        let remaining_entries: Vec<&TrashEntry> = kept_entries.iter().chain(failed_entries.iter()).collect();
+        Self::save_trash_entries(workspace_dir, &remaining_entries)?;
+
+        return Ok((deleted_entries, failed_entries));
+    }
+
+    /// Permanently deletes files and directories inside the trash that are not recorded in the trash manifest.
+    pub fn purge_untracked_trash(self: &Self) -> Result<Vec<PathBuf>, WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        let trash_dir = Path::new(TRASH_DIRNAME);
+        let entries = Self::load_trash_entries(workspace_dir)?;
+
+        let mut deleted_paths = Vec::new();
+
+        for dir_entry in fs_ops::read_directory(workspace_dir, trash_dir).map_err(WorkspaceError::from_io)? {
+            let dir_entry = dir_entry.map_err(WorkspaceError::from_io)?;
+            let relative_path = trash_dir.join(dir_entry.file_name());
+
+            // Skip the manifest itself and every recorded item
+            if dir_entry.file_name() == TRASH_MANIFEST_FILENAME {
+                continue;
+            }
+
+            if entries.iter().any(|e| e.get_trash_path() == relative_path) {
+                continue;
+            }
+
+            // Untracked folders, like ones left behind by a failed rollback, go with everything inside them
+            if dir_entry.path().is_dir() {
+                fs_ops::delete_dir_all(workspace_dir, &relative_path).map_err(WorkspaceError::from_io)?;
+            } else {
+                fs_ops::delete_file(workspace_dir, &relative_path).map_err(WorkspaceError::from_io)?;
+            }
+
+            deleted_paths.push(relative_path);
+        }
+
+        return Ok(deleted_paths);
+    }
+
     /// Creates a new folder with embedded metadata and saves it to the workspace.
     pub fn create_folder(
         self: &Self,
@@ -175,37 +531,259 @@ impl Workspace {
     }
 
     /// Saves folder's metadata content to the workspace.
-    /// TODO: changing folder's display name should trigger folder rename
+    /// Display name changes go through `rename_folder` so the directory name stays consistent.
     pub fn save_folder(self: &Self, folder: &Folder) -> Result<WorkspaceEvent, WorkspaceError> {
         let workspace_dir = self
             .workspace_dir
             .as_ref()
             .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
 
-        // Get the folder's metadata file
-        let metadata_path = folder.get_metadata_file_dir();
-        let metadata_file = fs_ops::open_file(workspace_dir, &metadata_path).map_err(WorkspaceError::from_io)?;
-
-        // Compose new data
-        let content_to_save = folder.compose();
-        fs_ops::write_file(workspace_dir, &metadata_path, &content_to_save).map_err(WorkspaceError::from_io)?;
+        // Folders adopted from plain directories get their metadata file here
+        Self::write_folder_metadata(workspace_dir, folder).map_err(WorkspaceError::from_io)?;
 
         return Ok(WorkspaceEvent::FolderContentSaved);
     }
 
-    pub fn delete_folder(self: &Self, folder: &Folder) -> Result<(), WorkspaceError> {
+    /// Writes the folder's metadata file if it doesn't have one yet.
+    /// A folder adopted from a plain directory keeps its ID in the index cache under its path,
+    /// so the file has to be written before the directory moves.
+    pub fn ensure_folder_metadata(self: &Self, folder: &Folder) -> Result<(), WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        if fs_ops::path_exists(workspace_dir, &folder.get_metadata_file_dir()) {
+            return Ok(());
+        }
+
+        Self::write_folder_metadata(workspace_dir, folder).map_err(WorkspaceError::from_io)?;
+
+        return Ok(());
+    }
+
+    /// Renames the folder's directory to match the new display name and rewrites its metadata file.
+    /// If rewriting the metadata fails, the directory is moved back to its old name.
+    pub fn rename_folder(self: &Self, folder: &Folder, new_display_name: &str) -> Result<PathBuf, WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        let current_dir = folder.get_relative_path();
+        let parent_dir = current_dir.parent().ok_or(WorkspaceError::InvalidPath)?;
+        let new_dir = parent_dir.join(Self::folder_dir_name(new_display_name, folder.get_id()));
+
+        // Move the directory only when the slug actually changes
+        if new_dir != current_dir {
+            if fs_ops::path_exists(workspace_dir, &new_dir) {
+                return Err(WorkspaceError::NameCollision);
+            }
+
+            fs_ops::move_dir(workspace_dir, current_dir, &new_dir).map_err(WorkspaceError::from_io)?;
+        }
+
+        // Keep everything else in the metadata, like keys from other tools, as it is
+        let mut metadata = folder.get_metadata().clone();
+        metadata.set_display_name(new_display_name);
+        let metadata_path = new_dir.join(METADATA_FILENAME);
+
+        if let Err(err) = fs_ops::write_file(workspace_dir, &metadata_path, &metadata.compose()) {
+            // Rollback the directory to its old name
+            if new_dir != current_dir {
+                let _ = fs_ops::move_dir(workspace_dir, &new_dir, current_dir);
+            }
+
+            return Err(WorkspaceError::from_io(err));
+        }
+
+        return Ok(new_dir);
+    }
+
+    /// Gives the folder a new ID by rewriting its metadata and renaming its directory to match.
+    pub fn reassign_folder_id(self: &Self, folder: &Folder, new_id: Uuid) -> Result<PathBuf, WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        let current_dir = folder.get_relative_path();
+        let parent_dir = current_dir.parent().ok_or(WorkspaceError::InvalidPath)?;
+        let new_dir = parent_dir.join(Self::folder_dir_name(folder.get_display_name(), new_id));
+
+        if fs_ops::path_exists(workspace_dir, &new_dir) {
+            return Err(WorkspaceError::NameCollision);
+        }
+
+        fs_ops::move_dir(workspace_dir, current_dir, &new_dir).map_err(WorkspaceError::from_io)?;
+
+        let mut metadata = folder.get_metadata().clone();
+        metadata.set_id(new_id);
+        let metadata_path = new_dir.join(METADATA_FILENAME);
+
+        if let Err(err) = fs_ops::write_file(workspace_dir, &metadata_path, &metadata.compose()) {
+            // Rollback the directory to its old name
+            let _ = fs_ops::move_dir(workspace_dir, &new_dir, current_dir);
+
+            return Err(WorkspaceError::from_io(err));
+        }
+
+        return Ok(new_dir);
+    }
+
+    /// Moves the folder's directory, along with everything inside, into the target folder directory.
+    pub fn move_folder(self: &Self, folder: &Folder, target_dir: &Path) -> Result<PathBuf, WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        // Folders can only be moved into regular folders
+        if Self::is_reserved_dir(target_dir) {
+            return Err(WorkspaceError::ReservedDirectory);
+        }
+
+        let current_dir = folder.get_relative_path();
+
+        let dir_name = current_dir.file_name().ok_or(WorkspaceError::InvalidPath)?;
+        let new_dir = target_dir.join(dir_name);
+
+        if fs_ops::path_exists(workspace_dir, &new_dir) {
+            return Err(WorkspaceError::NameCollision);
+        }
+
+        fs_ops::move_dir(workspace_dir, current_dir, &new_dir).map_err(WorkspaceError::from_io)?;
+
+        return Ok(new_dir);
+    }
+
+    /// Deletes an empty folder's metadata file and directory.
+    /// Fails without deleting anything else if the directory still has other content.
+    pub fn delete_empty_folder(self: &Self, folder: &Folder) -> Result<(), WorkspaceError> {
         let workspace_dir = self
             .workspace_dir
             .as_ref()
             .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
 
         let folder_dir = folder.get_relative_path();
+        let metadata_path = folder.get_metadata_file_dir();
+
+        // Folders adopted from plain directories have no metadata file
+        let has_metadata = fs_ops::path_exists(workspace_dir, &metadata_path);
+        if has_metadata {
+            fs_ops::delete_file(workspace_dir, &metadata_path).map_err(WorkspaceError::from_io)?;
+        }
+
+        if let Err(err) = fs_ops::delete_dir(workspace_dir, folder_dir) {
+            // Rollback the metadata file so the folder stays intact
+            if has_metadata && fs_ops::create_file(workspace_dir, &metadata_path).is_ok() {
+                let _ = fs_ops::write_file(workspace_dir, &metadata_path, &folder.compose());
+            }
+
+            return Err(WorkspaceError::from_io(err));
+        }
+
+        return Ok(());
+    }
+
+    /// Moves the folder's directory, with every note and folder inside it, into the trash as one item.
+    pub fn move_folder_to_trash(self: &Self, folder: &Folder) -> Result<(), WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        let trash_dir = Path::new(TRASH_DIRNAME);
+        fs_ops::ensure_dir(workspace_dir, trash_dir).map_err(WorkspaceError::from_io)?;
+
+        let current_dir = folder.get_relative_path();
+
+        let new_dir = trash_dir.join(Self::trash_name(current_dir, folder.get_id())?);
 
-        fs_ops::delete_dir(workspace_dir, folder_dir);
+        if fs_ops::path_exists(workspace_dir, &new_dir) {
+            return Err(WorkspaceError::NameCollision);
+        }
+
+        fs_ops::move_dir(workspace_dir, current_dir, &new_dir).map_err(WorkspaceError::from_io)?;
+
+        // Record where the folder came from so it can be restored
+        let entry = TrashEntry::new(
+            folder.get_id(),
+            folder.get_display_name(),
+            folder.get_parent_id(),
+            current_dir.to_path_buf(),
+            new_dir.clone(),
+            Self::now_timestamp(),
+            true,
+        );
+
+        if let Err(err) = Self::add_trash_entry(workspace_dir, entry) {
+            // Rollback the folder out of trash
+            let _ = fs_ops::move_dir(workspace_dir, &new_dir, current_dir);
+
+            return Err(err);
+        }
 
         return Ok(());
     }
 
+    /// Moves a trashed folder back into the target folder directory and removes it from the trash manifest.
+    /// Returns every note and folder inside the restored folder, the folder itself first.
+    pub fn restore_folder(
+        self: &Self,
+        id: Uuid,
+        target_dir: &Path,
+        parent_id: Uuid,
+        has_lazy_bodies: bool,
+    ) -> Result<(Vec<Note>, Vec<Folder>), WorkspaceError> {
+        let workspace_dir = self
+            .workspace_dir
+            .as_ref()
+            .ok_or(WorkspaceError::NoWorkspaceDirectoryFound)?;
+
+        let mut entries = Self::load_trash_entries(workspace_dir)?;
+
+        let position = entries
+            .iter()
+            .position(|e| e.get_id() == id && e.is_folder())
+            .ok_or(WorkspaceError::NotFound)?;
+        let entry = entries.remove(position);
+
+        let trash_dir = entry.get_trash_path();
+
+        let dir_name = entry
+            .get_original_path()
+            .file_name()
+            .ok_or(WorkspaceError::InvalidPath)?;
+        let new_dir = target_dir.join(dir_name);
+
+        if fs_ops::path_exists(workspace_dir, &new_dir) {
+            return Err(WorkspaceError::NameCollision);
+        }
+
+        fs_ops::move_dir(workspace_dir, trash_dir, &new_dir).map_err(WorkspaceError::from_io)?;
+
+        let remaining_entries: Vec<&TrashEntry> = entries.iter().collect();
+        if let Err(err) = Self::save_trash_entries(workspace_dir, &remaining_entries) {
+            // Rollback the folder into trash
+            let _ = fs_ops::move_dir(workspace_dir, &new_dir, trash_dir);
+
+            return Err(err);
+        }
+
+        // Restored folders are read from disk and cached on the next scan,
+        // files that can't be loaded are left out like they are when syncing
+        return Self::load_folder_tree(
+            workspace_dir,
+            &new_dir,
+            parent_id,
+            has_lazy_bodies,
+            &mut IndexCache::new(),
+            &mut HashMap::new(),
+            &mut ScanReport::new(),
+        );
+    }
+
     // TODO: folder를 로드할 때 하위에 포함된 모든 note도 함께 로드해야 하지 않나?
     // /// Loads a folder and it's metadata from the specified folder path.
     // pub fn load_folder(self: &Self, folder_dir: &Path, parent_id: Uuid) -> Result<Folder, WorkspaceError> {
@@ -216,8 +794,124 @@ impl Workspace {
     //     return Ok(Folder::from_data(folder_dir.to_path_buf(), data, parent_id));
     // }
 
+    /// Reads the note file into a Note, along with the file's timestamps.
+    fn load_note_file(workspace_dir: &Path, file_path: &Path, parent_id: Uuid) -> Result<Note, WorkspaceError> {
+        let data = Self::load_note_data(workspace_dir, file_path)?;
+        let (created_at, modified_at) =
+            fs_ops::file_times(workspace_dir, file_path).map_err(WorkspaceError::from_io)?;
+
+        let mut note = Note::from_data(file_path.to_path_buf(), data, parent_id);
+        note.set_timestamps(created_at, modified_at);
+
+        return Ok(note);
+    }
+
+    /// Loads the note from the cache if the file didn't change since it was cached, otherwise reads and parses it.
+    /// The cache holds no bodies, without lazy bodies the body of a cached note is read from the file
+    /// without parsing its front matter. The file's stamp is recorded in `stamps` for the next cache.
+    fn load_cached_note_file(
+        workspace_dir: &Path,
+        file_path: &Path,
+        parent_id: Uuid,
+        has_lazy_bodies: bool,
+        cache: &mut IndexCache,
+        stamps: &mut HashMap<PathBuf, FileStamp>,
+        report: &mut ScanReport,
+    ) -> Result<Note, WorkspaceError> {
+        let (modified, size) = fs_ops::file_stat(workspace_dir, file_path).map_err(WorkspaceError::from_io)?;
+
+        let cached = cache.take_note(file_path);
+        let is_unchanged = cached
+            .as_ref()
+            .is_some_and(|cached| cached.stamp.modified == modified && cached.stamp.size == size);
+
+        if !is_unchanged {
+            cache.mark_outdated();
+        }
+
+        // Content read to check the hash of a touched file, kept for its body
+        let mut read_content = None;
+
+        let (mut data, indexed_body, created_at, hash, is_cached) = match cached {
+            Some(cached) if is_unchanged => (
+                cached.data,
+                cached.indexed_body,
+                cached.created_at,
+                cached.stamp.hash,
+                true,
+            ),
+            cached => {
+                let raw_content = Self::read_note_content(workspace_dir, file_path)?;
+                let hash = content_hash(&raw_content);
+                let (created_at, _) = fs_ops::file_times(workspace_dir, file_path).map_err(WorkspaceError::from_io)?;
+
+                match cached {
+                    // Touched without changing the content, no need to parse it again
+                    Some(cached) if cached.stamp.hash == hash => {
+                        read_content = Some(raw_content);
+                        (cached.data, cached.indexed_body, created_at, hash, true)
+                    }
+                    _ => {
+                        let data = parse_note(raw_content).ok_or(WorkspaceError::MalformedFrontMatter)?;
+                        let indexed_body = IndexedBody::from_body(&data.body);
+                        (data, indexed_body, created_at, hash, false)
+                    }
+                }
+            }
+        };
+
+        if is_cached && !has_lazy_bodies {
+            let raw_content = match read_content {
+                Some(raw_content) => raw_content,
+                None => Self::read_note_content(workspace_dir, file_path)?,
+            };
+
+            let (_, body) = split_note(&raw_content).ok_or(WorkspaceError::MalformedFrontMatter)?;
+            data.body = body.to_string();
+        }
+
+        let has_id = data.id.is_some();
+        let mut note = Note::from_data(file_path.to_path_buf(), data, parent_id);
+        note.set_indexed_body(indexed_body);
+
+        // With lazy bodies, the body is dropped as soon as the indexes' data is taken from it
+        // and read from disk again when it's needed
+        if has_lazy_bodies {
+            note.unload_body();
+        }
+
+        if has_id {
+            stamps.insert(file_path.to_path_buf(), FileStamp::new(modified, size, hash));
+            note.set_timestamps(created_at, modified / NANOS_PER_SECOND);
+
+            return Ok(note);
+        }
+
+        // Write the generated ID into the front matter so the note keeps it on the next sync,
+        // the rewritten file is left out of the cache until it's read again
+        let modified = match Self::write_note_id(workspace_dir, file_path, note.get_id()) {
+            Ok(_) => fs_ops::file_stat(workspace_dir, file_path).map_or(modified, |(modified, _)| modified),
+            Err(err) => {
+                let unsaved_id = UnsavedId::new(file_path.to_path_buf(), false, err.to_string());
+                report.unsaved_ids.push(unsaved_id);
+                modified
+            }
+        };
+        note.set_timestamps(created_at, modified / NANOS_PER_SECOND);
+
+        return Ok(note);
+    }
+
     /// Reads and parses the raw disk content into a NoteData object.
     fn load_note_data(workspace_dir: &Path, file_path: &Path) -> Result<NoteData, WorkspaceError> {
+        let raw_content = Self::read_note_content(workspace_dir, file_path)?;
+
+        let data = parse_note(raw_content).ok_or(WorkspaceError::MalformedFrontMatter)?;
+
+        return Ok(data);
+    }
+
+    fn read_note_content(workspace_dir: &Path, file_path: &Path) -> Result<String, WorkspaceError> {
         let mut file = fs_ops::open_file(workspace_dir, file_path).map_err(WorkspaceError::from_io)?;
 
         // Read file's content
@@ -229,13 +923,214 @@ impl Workspace {
             _ => WorkspaceError::from_io(err),
         })?;
 
-        let data = parse_note(raw_content);
+        return Ok(raw_content);
+    }
+
+    /// Adds the ID line at the top of the note file's front matter, leaving every other line as it is.
+    /// A note without front matter is given one holding only the ID.
+    fn write_note_id(workspace_dir: &Path, file_path: &Path, id: Uuid) -> Result<(), WorkspaceError> {
+        let raw_content = Self::read_note_content(workspace_dir, file_path)?;
+
+        let (byte_order_mark, content) = match raw_content.strip_prefix('\u{feff}') {
+            Some(content) => ("\u{feff}", content),
+            None => ("", raw_content.as_str()),
+        };
+
+        let line_break = match LineEnding::detect(content) {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        };
+        let id_line = format!("id: \"{}\"{}", id, line_break);
+
+        let (front_matter, _) = split_note(content).ok_or(WorkspaceError::MalformedFrontMatter)?;
+
+        let content_to_save = if front_matter.is_empty() {
+            format!(
+                "{}---{}{}---{}{}",
+                byte_order_mark, line_break, id_line, line_break, content
+            )
+        } else {
+            // The opening delimiter is the first line
+            let (opening_delimiter, rest) = content.split_at(content.find('\n').map_or(content.len(), |i| i + 1));
+            format!("{}{}{}{}", byte_order_mark, opening_delimiter, id_line, rest)
+        };
+
+        fs_ops::write_file(workspace_dir, file_path, &content_to_save).map_err(WorkspaceError::from_io)?;
+
+        return Ok(());
+    }
+
+    /// Replaces the front matter of the note file while keeping the body stored on disk.
+    fn write_note_metadata(
+        workspace_dir: &Path,
+        file_path: &Path,
+        metadata: &NoteMetadata,
+    ) -> Result<(), WorkspaceError> {
+        let data = Self::load_note_data(workspace_dir, file_path)?;
+
+        let mut content_to_save = metadata.compose();
+        content_to_save.push_str(&data.body);
+
+        fs_ops::write_file(workspace_dir, file_path, &content_to_save).map_err(WorkspaceError::from_io)?;
+
+        return Ok(());
+    }
+
+    /// Reads the trash manifest, a missing manifest means the trash is empty.
+    fn load_trash_entries(workspace_dir: &Path) -> Result<Vec<TrashEntry>, WorkspaceError> {
+        let manifest_path = Path::new(TRASH_DIRNAME).join(TRASH_MANIFEST_FILENAME);
+
+        if !fs_ops::path_exists(workspace_dir, &manifest_path) {
+            return Ok(Vec::new());
+        }
+
+        let mut manifest_file = fs_ops::open_file(workspace_dir, &manifest_path).map_err(WorkspaceError::from_io)?;
+
+        let mut file_content = String::new();
+        manifest_file
+            .read_to_string(&mut file_content)
+            .map_err(WorkspaceError::from_io)?;
+
+        return Ok(parse_trash(file_content));
+    }
+
+    /// Overwrites the trash manifest with the given entries.
+    fn save_trash_entries(workspace_dir: &Path, entries: &[&TrashEntry]) -> Result<(), WorkspaceError> {
+        let manifest_path = Path::new(TRASH_DIRNAME).join(TRASH_MANIFEST_FILENAME);
+
+        if !fs_ops::path_exists(workspace_dir, &manifest_path) {
+            fs_ops::create_file(workspace_dir, &manifest_path).map_err(WorkspaceError::from_io)?;
+        }
+
+        let content_to_save: String = entries.iter().map(|e| e.compose()).collect();
+        fs_ops::write_file(workspace_dir, &manifest_path, &content_to_save).map_err(WorkspaceError::from_io)?;
+
+        return Ok(());
+    }
+
+    /// Appends an entry to the trash manifest, replacing any older entry with the same ID.
+    fn add_trash_entry(workspace_dir: &Path, entry: TrashEntry) -> Result<(), WorkspaceError> {
+        let mut entries = Self::load_trash_entries(workspace_dir)?;
+
+        entries.retain(|e| e.get_id() != entry.get_id());
+        entries.push(entry);
+
+        let entries: Vec<&TrashEntry> = entries.iter().collect();
+        return Self::save_trash_entries(workspace_dir, &entries);
+    }
+
+    /// Reads the workspace settings file, a missing file means default settings.
+    fn load_settings(workspace_dir: &Path) -> Result<WorkspaceSettings, WorkspaceError> {
+        let settings_path = Path::new(SETTINGS_FILENAME);
+
+        if !fs_ops::path_exists(workspace_dir, settings_path) {
+            return Ok(WorkspaceSettings::new());
+        }
+
+        let mut settings_file = fs_ops::open_file(workspace_dir, settings_path).map_err(WorkspaceError::from_io)?;
+
+        let mut file_content = String::new();
+        settings_file
+            .read_to_string(&mut file_content)
+            .map_err(WorkspaceError::from_io)?;
+
+        return Ok(parse_workspace_settings(file_content));
+    }
+
+    /// Reads the index cache, a missing or unreadable cache is treated as empty.
+    fn load_index_cache(workspace_dir: &Path) -> IndexCache {
+        let cache_path = Path::new(CACHE_DIRNAME).join(INDEX_CACHE_FILENAME);
+
+        let Ok(mut cache_file) = fs_ops::open_file(workspace_dir, &cache_path) else {
+            return IndexCache::new();
+        };
+
+        let mut file_content = String::new();
+        if cache_file.read_to_string(&mut file_content).is_err() {
+            return IndexCache::new();
+        }
+
+        return parse_index_cache(file_content);
+    }
+
+    fn save_index_cache(workspace_dir: &Path, content: &str) -> Result<(), WorkspaceError> {
+        let cache_path = Path::new(CACHE_DIRNAME).join(INDEX_CACHE_FILENAME);
+
+        if !fs_ops::path_exists(workspace_dir, &cache_path) {
+            fs_ops::create_file(workspace_dir, &cache_path).map_err(WorkspaceError::from_io)?;
+        }
+
+        fs_ops::write_file(workspace_dir, &cache_path, content).map_err(WorkspaceError::from_io)?;
+
+        return Ok(());
+    }
+
+    fn now_timestamp() -> u64 {
+        return SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+    }
+
+    /// Loads the folder's metadata from the cache if its metadata file didn't change since it was cached,
+    /// otherwise reads it. The metadata file's stamp is recorded in `stamps` under the folder's directory.
+    fn load_cached_folder_data(
+        workspace_dir: &Path,
+        folder_dir: &Path,
+        cache: &mut IndexCache,
+        stamps: &mut HashMap<PathBuf, FileStamp>,
+    ) -> Result<FolderData, WorkspaceError> {
+        let metadata_path = folder_dir.join(METADATA_FILENAME);
+
+        // Plain directories are adopted as folders named after the directory,
+        // the ID generated for one is kept in the cache until its metadata file is written
+        if !fs_ops::path_exists(workspace_dir, &metadata_path) {
+            let mut data = FolderData::new();
+            data.id = cache.take_directory(folder_dir);
+
+            if data.id.is_none() {
+                cache.mark_outdated();
+            }
+
+            return Ok(data);
+        }
+
+        let (modified, size) = fs_ops::file_stat(workspace_dir, &metadata_path).map_err(WorkspaceError::from_io)?;
+
+        let (data, hash) = match cache.take_folder(folder_dir) {
+            Some(cached) if cached.stamp.modified == modified && cached.stamp.size == size => {
+                (cached.data, cached.stamp.hash)
+            }
+            _ => {
+                cache.mark_outdated();
+
+                let file_content = Self::read_folder_metadata(workspace_dir, folder_dir)?;
+                let hash = content_hash(&file_content);
+
+                let data = parse_folder(file_content).ok_or(WorkspaceError::MalformedFrontMatter)?;
+                (data, hash)
+            }
+        };
+
+        stamps.insert(folder_dir.to_path_buf(), FileStamp::new(modified, size, hash));
 
         return Ok(data);
     }
 
-    /// Reads and parses the raw disk content into a FolderData object.
-    fn load_folder_data(workspace_dir: &Path, folder_dir: &Path) -> Result<FolderData, WorkspaceError> {
+    /// Writes the folder's metadata file, creating it for directories that don't have one yet.
+    fn write_folder_metadata(workspace_dir: &Path, folder: &Folder) -> std::io::Result<()> {
+        let metadata_path = folder.get_metadata_file_dir();
+
+        if !fs_ops::path_exists(workspace_dir, &metadata_path) {
+            fs_ops::create_file(workspace_dir, &metadata_path)?;
+        }
+
+        fs_ops::write_file(workspace_dir, &metadata_path, &folder.compose())?;
+
+        return Ok(());
+    }
+
+    fn read_folder_metadata(workspace_dir: &Path, folder_dir: &Path) -> Result<String, WorkspaceError> {
         let metadata_path = folder_dir.join(METADATA_FILENAME);
 
         let mut metadata_file = fs_ops::open_file(workspace_dir, &metadata_path).map_err(WorkspaceError::from_io)?;
@@ -246,9 +1141,7 @@ impl Workspace {
             .read_to_string(&mut file_content)
             .map_err(WorkspaceError::from_io)?;
 
-        let data = parse_folder(file_content);
-
-        return Ok(data);
+        return Ok(file_content);
     }
 
     /// Creates a new note file with a name composed of
@@ -259,11 +1152,7 @@ impl Workspace {
         title_name: &str,
         note_id: Uuid,
     ) -> Result<PathBuf, WorkspaceError> {
-        // Sanitize the title name to ensure valid file name
-        let base_name = sanitize_name(title_name, MAX_FILENAME_LEN);
-
-        let filename = format!("{}____{}.{}", base_name, note_id.to_string(), NOTE_FILE_EXTENSION);
-        let relative_file_path = parent_dir.join(&filename);
+        let relative_file_path = parent_dir.join(Self::note_file_name(title_name, note_id));
 
         match fs_ops::create_file(workspace_dir, &relative_file_path) {
             Ok(_) => return Ok(relative_file_path),
@@ -272,6 +1161,45 @@ impl Workspace {
         }
     }
 
+    /// Returns true if the directory is inside the trash or cache directories.
+    fn is_reserved_dir(dir: &Path) -> bool {
+        return match dir.components().next() {
+            Some(component) => component.as_os_str() == TRASH_DIRNAME || component.as_os_str() == CACHE_DIRNAME,
+            None => false,
+        };
+    }
+
+    /// Composes the name of a trashed item from its current name with the ID appended,
+    /// unless the name already ends with it, so trashed items never share a name.
+    fn trash_name(current_path: &Path, id: Uuid) -> Result<String, WorkspaceError> {
+        let stem = current_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or(WorkspaceError::InvalidPath)?;
+
+        let id_suffix = format!("____{}", id);
+        let mut name = if stem.ends_with(&id_suffix) {
+            stem.to_string()
+        } else {
+            format!("{}{}", stem, id_suffix)
+        };
+
+        if let Some(extension) = current_path.extension().and_then(|extension| extension.to_str()) {
+            name.push('.');
+            name.push_str(extension);
+        }
+
+        return Ok(name);
+    }
+
+    /// Composes a note file name from the slugified title name and the unique note ID.
+    fn note_file_name(title_name: &str, note_id: Uuid) -> String {
+        // Sanitize the title name to ensure valid file name
+        let base_name = sanitize_name(title_name, MAX_FILENAME_LEN);
+
+        return format!("{}____{}.{}", base_name, note_id, NOTE_FILE_EXTENSION);
+    }
+
     /// Creates a new folder directory with a name composed of
     /// the slugified display name and the unique folder ID.
     fn create_folder_dir(
@@ -280,11 +1208,7 @@ impl Workspace {
         display_name: &str,
         folder_id: Uuid,
     ) -> Result<PathBuf, WorkspaceError> {
-        // Sanitize the display name to ensure valid folder name
-        let base_name = sanitize_name(display_name, MAX_FOLDERNAME_LEN);
-
-        let folder_name = format!("{}____{}", base_name, folder_id.to_string());
-        let relative_folder_dir = parent_dir.join(&folder_name);
+        let relative_folder_dir = parent_dir.join(Self::folder_dir_name(display_name, folder_id));
 
         match fs_ops::create_dir(workspace_dir, &relative_folder_dir) {
             Ok(_) => return Ok(relative_folder_dir),
@@ -293,28 +1217,47 @@ impl Workspace {
         }
     }
 
+    /// Composes a folder directory name from the slugified display name and the unique folder ID.
+    fn folder_dir_name(display_name: &str, folder_id: Uuid) -> String {
+        // Sanitize the display name to ensure valid folder name
+        let base_name = sanitize_name(display_name, MAX_FOLDERNAME_LEN);
+
+        return format!("{}____{}", base_name, folder_id);
+    }
+
     ///
     fn scan_directory(
         workspace_dir: &Path,
         current_dir: &Path,
         parent_id: Uuid,
+        has_lazy_bodies: bool,
+        cache: &mut IndexCache,
+        stamps: &mut HashMap<PathBuf, FileStamp>,
+        report: &mut ScanReport,
     ) -> Result<(Vec<Note>, Vec<Folder>), WorkspaceError> {
         let mut notes = Vec::new();
         let mut folders = Vec::new();
 
         for entry in fs_ops::read_directory(workspace_dir, current_dir).map_err(WorkspaceError::from_io)? {
-            let entry = entry.map_err(WorkspaceError::from_io)?;
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    let reason = SkipReason::from_workspace(WorkspaceError::from_io(err));
+                    report.skipped.push(SkippedFile::new(current_dir.to_path_buf(), reason));
+                    continue;
+                }
+            };
             let entry_name = entry.file_name();
 
             let entry_name_str = entry_name.to_string_lossy();
 
-            // Skip folder metadata files
-            if entry_name_str == METADATA_FILENAME {
+            // Skip folder metadata and workspace settings files
+            if entry_name_str == METADATA_FILENAME || entry_name_str == SETTINGS_FILENAME {
                 continue;
             }
 
             // Skip trash and cache folders
-            if entry_name_str == ".trash" || entry_name_str == ".cache" {
+            if entry_name_str == TRASH_DIRNAME || entry_name_str == CACHE_DIRNAME {
                 continue;
             }
 
@@ -325,42 +1268,91 @@ impl Workspace {
             if entry_path.is_file() {
                 // Note is just a text file
                 if entry_path.extension().and_then(|e: &std::ffi::OsStr| e.to_str()) == Some("txt") {
-                    let note_data = Self::load_note_data(workspace_dir, &relative_path)?;
-
-                    let note = Note::from_data(relative_path, note_data);
-                    notes.push(note);
+                    match Self::load_cached_note_file(
+                        workspace_dir,
+                        &relative_path,
+                        parent_id,
+                        has_lazy_bodies,
+                        cache,
+                        stamps,
+                        report,
+                    ) {
+                        Ok(note) => notes.push(note),
+                        Err(err) => {
+                            let reason = SkipReason::from_workspace(err);
+                            report.skipped.push(SkippedFile::new(relative_path, reason));
+                        }
+                    }
                 }
                 continue;
             }
 
-            // This is a folder
-            if entry_path.is_dir() {
-                let folder_dir = relative_path;
-
-                let folder_data = Self::load_folder_data(workspace_dir, &folder_dir)?;
-                let mut folder = Folder::from_data(folder_dir.clone(), folder_data, parent_id);
-                let folder_id = folder.get_id();
-
-                // Recurse into subfolder
-                let (child_notes, child_folders) = Self::scan_directory(workspace_dir, &folder_dir, folder_id)?;
-
-                // Collect all notes whithin this folder
-                for child_note in &child_notes {
-                    folder.add_child_note(child_note.get_id());
+            // This is a folder, hidden directories such as `.git` are left alone
+            if entry_path.is_dir() && !entry_name_str.starts_with('.') {
+                // A folder that can't be loaded is skipped along with everything inside it
+                match Self::load_folder_tree(
+                    workspace_dir,
+                    &relative_path,
+                    parent_id,
+                    has_lazy_bodies,
+                    cache,
+                    stamps,
+                    report,
+                ) {
+                    Ok((child_notes, child_folders)) => {
+                        notes.extend(child_notes);
+                        folders.extend(child_folders);
+                    }
+                    Err(err) => {
+                        let reason = SkipReason::from_workspace(err);
+                        report.skipped.push(SkippedFile::new(relative_path, reason));
+                    }
                 }
+            }
+        }
 
-                // Collect all folders whithin this folder
-                for child_folder in &child_folders {
-                    folder.add_child_folder(child_folder.get_id());
-                }
+        return Ok((notes, folders));
+    }
 
-                folders.push(folder);
+    /// Loads the folder along with every note and folder inside it.
+    /// The folder itself is the first of the returned folders.
+    fn load_folder_tree(
+        workspace_dir: &Path,
+        folder_dir: &Path,
+        parent_id: Uuid,
+        has_lazy_bodies: bool,
+        cache: &mut IndexCache,
+        stamps: &mut HashMap<PathBuf, FileStamp>,
+        report: &mut ScanReport,
+    ) -> Result<(Vec<Note>, Vec<Folder>), WorkspaceError> {
+        let folder_data = Self::load_cached_folder_data(workspace_dir, folder_dir, cache, stamps)?;
+        let mut folder = Folder::from_data(folder_dir.to_path_buf(), folder_data, parent_id);
+        let folder_id = folder.get_id();
+
+        // Recurse into subfolder
+        let (child_notes, child_folders) = Self::scan_directory(
+            workspace_dir,
+            folder_dir,
+            folder_id,
+            has_lazy_bodies,
+            cache,
+            stamps,
+            report,
+        )?;
+
+        // Collect notes directly whithin this folder
+        for child_note in child_notes.iter().filter(|n| n.get_parent_id() == folder_id) {
+            folder.add_child_note(child_note.get_id());
+        }
 
-                notes.extend(child_notes);
-                folders.extend(child_folders);
-            }
+        // Collect folders directly whithin this folder
+        for child_folder in child_folders.iter().filter(|f| f.get_parent_id() == folder_id) {
+            folder.add_child_folder(child_folder.get_id());
         }
 
-        return Ok((notes, folders));
+        let mut folders = vec![folder];
+        folders.extend(child_folders);
+
+        return Ok((child_notes, folders));
     }
 }
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::io;
 
 #[derive(Debug)]
@@ -7,11 +8,13 @@ pub enum WorkspaceError {
     PermissionDenied,
 
     CorruptedFile,
+    MalformedFrontMatter,
 
     InvalidPath,
     NotFound,
 
     NameCollision,
+    ReservedDirectory,
 
     Unknown(String),
 }
@@ -28,3 +31,20 @@ impl WorkspaceError {
         }
     }
 }
+
+impl fmt::Display for WorkspaceError {
+    fn fmt(self: &Self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoWorkspaceDirectoryFound => return write!(f, "no workspace directory"),
+            Self::StorageFull => return write!(f, "storage full"),
+            Self::PermissionDenied => return write!(f, "permission denied"),
+            Self::CorruptedFile => return write!(f, "not valid UTF-8"),
+            Self::MalformedFrontMatter => return write!(f, "malformed front matter"),
+            Self::InvalidPath => return write!(f, "invalid path"),
+            Self::NotFound => return write!(f, "not found"),
+            Self::NameCollision => return write!(f, "name already taken"),
+            Self::ReservedDirectory => return write!(f, "reserved directory"),
+            Self::Unknown(err) => return write!(f, "{}", err),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use scrap::Scrap;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::{Uuid, uuid};
+
+// Not every test file uses every helper
+#[allow(dead_code)]
+pub const WORKSPACE_ID: Uuid = uuid!("3e206920-6c75-7620-7520-6d722063656f");
+
+/// Creates an empty directory for a test workspace, removing what an earlier run left behind.
+pub fn temp_workspace(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("scrap-{}-{}", name, std::process::id()));
+
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    return dir;
+}
+
+pub fn open_workspace(dir: &Path) -> Scrap {
+    let mut scrap = Scrap::new();
+    scrap.set_workspace(dir).unwrap();
+    scrap.sync_workspace().unwrap();
+
+    return scrap;
+}
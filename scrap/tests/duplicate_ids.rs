@@ -0,0 +1,122 @@
+mod common;
+
+use common::{WORKSPACE_ID, open_workspace, temp_workspace};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use uuid::uuid;
+
+/// Copies the directory the way a file manager would, the copy gets new modification times.
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+
+        if entry.path().is_dir() {
+            copy_dir(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+/// Moves the modification times of every file out of the window in which files are left out of the cache.
+fn backdate_files(dir: &Path) {
+    let modified = SystemTime::now() - Duration::from_secs(60);
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+
+        if path.is_dir() {
+            backdate_files(&path);
+        } else {
+            let file = fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(modified).unwrap();
+        }
+    }
+}
+
+#[test]
+fn indexed_folder_keeps_its_id_over_a_shorter_copy() {
+    let dir = temp_workspace("duplicate-indexed-folder");
+    let mut scrap = open_workspace(&dir);
+
+    let folder_id = scrap.create_folder(WORKSPACE_ID, "Projects".to_string()).unwrap();
+    let note_id = scrap
+        .create_note(folder_id, "Plan".to_string(), "text".to_string())
+        .unwrap();
+
+    let folder_dir = PathBuf::from(format!("projects____{}", folder_id));
+    let note_path = folder_dir.join(format!("plan____{}.txt", note_id));
+
+    // The copy's path is shorter and holds the ID too, only the index tells them apart
+    let copy_dir_path = PathBuf::from(format!("p____{}", folder_id));
+    copy_dir(&dir.join(&folder_dir), &dir.join(&copy_dir_path));
+
+    let report = scrap.sync_workspace().unwrap();
+
+    assert_eq!(report.id_conflicts[0].id, folder_id);
+    assert_eq!(report.id_conflicts[0].kept_path, folder_dir);
+    assert!(report.id_conflicts[0].new_id.is_some());
+    assert!(!dir.join(&copy_dir_path).exists());
+
+    assert!(dir.join(&folder_dir).exists());
+    assert_eq!(scrap.list_children(folder_id).unwrap().relative_path, folder_dir);
+    assert_eq!(scrap.get_note(note_id).unwrap().relative_path, note_path);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cached_folder_keeps_its_id_over_a_shorter_copy() {
+    let dir = temp_workspace("duplicate-cached-folder");
+    let mut scrap = open_workspace(&dir);
+
+    let folder_id = scrap.create_folder(WORKSPACE_ID, "Projects".to_string()).unwrap();
+    let note_id = scrap
+        .create_note(folder_id, "Plan".to_string(), "text".to_string())
+        .unwrap();
+
+    let folder_dir = PathBuf::from(format!("projects____{}", folder_id));
+    let note_path = folder_dir.join(format!("plan____{}.txt", note_id));
+
+    // A sync in another session caches the folder and the note
+    backdate_files(&dir);
+    open_workspace(&dir);
+
+    let copy_dir_path = PathBuf::from(format!("p____{}", folder_id));
+    copy_dir(&dir.join(&folder_dir), &dir.join(&copy_dir_path));
+
+    // A new session starts with an empty index, only the cache knows which one is the original
+    let mut scrap = open_workspace(&dir);
+
+    assert!(dir.join(&folder_dir).exists());
+    assert!(!dir.join(&copy_dir_path).exists());
+    assert_eq!(scrap.list_children(folder_id).unwrap().relative_path, folder_dir);
+    assert_eq!(scrap.get_note(note_id).unwrap().relative_path, note_path);
+    assert_eq!(scrap.list_notes().unwrap().len(), 2);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn note_named_after_its_id_keeps_it_over_a_shorter_copy() {
+    let dir = temp_workspace("duplicate-named-note");
+    let id = uuid!("5d1c7e2a-93b4-4f60-8a2e-7c4b1f0d9e36");
+
+    let note_path = PathBuf::from(format!("plan____{}.txt", id));
+    let content = format!("---\nid: \"{}\"\ntitle: \"Plan\"\n---\n", id);
+    fs::write(dir.join(&note_path), &content).unwrap();
+    fs::write(dir.join("a.txt"), &content).unwrap();
+
+    let mut scrap = open_workspace(&dir);
+
+    assert_eq!(scrap.get_note(id).unwrap().relative_path, note_path);
+    assert_eq!(fs::read_to_string(dir.join(&note_path)).unwrap(), content);
+    assert_eq!(scrap.list_notes().unwrap().len(), 2);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,216 @@
+mod common;
+
+use common::{WORKSPACE_ID, open_workspace, temp_workspace};
+use scrap::ScrapError;
+
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn rename_folder_rebases_paths_inside_it() {
+    let dir = temp_workspace("rename-folder");
+    let mut scrap = open_workspace(&dir);
+
+    let folder_id = scrap.create_folder(WORKSPACE_ID, "Projects".to_string()).unwrap();
+    let child_id = scrap.create_folder(folder_id, "Scrap".to_string()).unwrap();
+    let note_id = scrap
+        .create_note(child_id, "Plan".to_string(), "text".to_string())
+        .unwrap();
+
+    scrap.rename_folder(folder_id, "Work".to_string()).unwrap();
+
+    let new_dir = PathBuf::from(format!("work____{}", folder_id));
+    let child_dir = new_dir.join(format!("scrap____{}", child_id));
+    let note_path = child_dir.join(format!("plan____{}.txt", note_id));
+
+    assert!(!dir.join(format!("projects____{}", folder_id)).exists());
+    assert!(
+        fs::read_to_string(dir.join(new_dir.join("_metadata.txt")))
+            .unwrap()
+            .contains("display-name: \"Work\"\n")
+    );
+
+    let folder = scrap.list_children(folder_id).unwrap();
+    assert_eq!(folder.display_name, "Work");
+    assert_eq!(folder.relative_path, new_dir);
+    assert_eq!(folder.folders[0].relative_path, child_dir);
+
+    // Notes inside are still read from their rebased path
+    let note = scrap.get_note(note_id).unwrap();
+    assert_eq!(note.relative_path, note_path);
+    assert_eq!(note.folder_id, child_id);
+
+    // The same IDs are read back from disk
+    let mut scrap = open_workspace(&dir);
+    assert_eq!(scrap.list_children(folder_id).unwrap().display_name, "Work");
+    assert_eq!(scrap.get_note(note_id).unwrap().folder_id, child_id);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn move_folder_rejects_cycles() {
+    let dir = temp_workspace("move-folder-cycle");
+    let mut scrap = open_workspace(&dir);
+
+    let parent_id = scrap.create_folder(WORKSPACE_ID, "Parent".to_string()).unwrap();
+    let child_id = scrap.create_folder(parent_id, "Child".to_string()).unwrap();
+    let grandchild_id = scrap.create_folder(child_id, "Grandchild".to_string()).unwrap();
+
+    for target_id in [parent_id, child_id, grandchild_id] {
+        match scrap.move_folder(parent_id, target_id) {
+            Err(ScrapError::FolderCycle(id, target)) => assert_eq!((id, target), (parent_id, target_id)),
+            _ => panic!("moving a folder into {} should be rejected", target_id),
+        }
+    }
+
+    // Nothing moved on disk
+    let parent_dir = dir.join(format!("parent____{}", parent_id));
+    assert!(parent_dir.join(format!("child____{}", child_id)).is_dir());
+
+    // Moving a folder out of its parent is fine
+    scrap.move_folder(grandchild_id, WORKSPACE_ID).unwrap();
+    assert!(dir.join(format!("grandchild____{}", grandchild_id)).is_dir());
+    assert_eq!(scrap.list_children(grandchild_id).unwrap().parent_id, WORKSPACE_ID);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn trashed_folder_is_restored_with_its_content() {
+    let dir = temp_workspace("trash-folder");
+    let mut scrap = open_workspace(&dir);
+
+    let parent_id = scrap.create_folder(WORKSPACE_ID, "Parent".to_string()).unwrap();
+    let folder_id = scrap.create_folder(parent_id, "Archive".to_string()).unwrap();
+    let child_id = scrap.create_folder(folder_id, "2025".to_string()).unwrap();
+    let note_id = scrap
+        .create_note(child_id, "Report".to_string(), "text".to_string())
+        .unwrap();
+    let note_path = scrap.get_note(note_id).unwrap().relative_path;
+
+    scrap.remove_folder(folder_id).unwrap();
+
+    let folder_dir = dir
+        .join(format!("parent____{}", parent_id))
+        .join(format!("archive____{}", folder_id));
+    assert!(!folder_dir.exists());
+    assert!(matches!(scrap.get_note(note_id), Err(ScrapError::NoteNotFound(_))));
+    assert!(matches!(
+        scrap.list_children(child_id),
+        Err(ScrapError::FolderNotFound(_))
+    ));
+
+    // The folder is one item in the trash, the notes and folders inside aren't listed
+    let trash = scrap.list_trash().unwrap();
+    assert_eq!(trash.len(), 1);
+    assert!(trash[0].is_folder);
+    assert_eq!((trash[0].id, trash[0].original_folder_id), (folder_id, parent_id));
+
+    assert_eq!(scrap.restore_folder(folder_id).unwrap(), parent_id);
+
+    assert!(folder_dir.is_dir());
+    assert!(scrap.list_trash().unwrap().is_empty());
+    assert_eq!(scrap.list_children(folder_id).unwrap().parent_id, parent_id);
+    assert_eq!(scrap.list_children(child_id).unwrap().parent_id, folder_id);
+
+    let note = scrap.get_note(note_id).unwrap();
+    assert_eq!(note.relative_path, note_path);
+    assert_eq!(note.folder_id, child_id);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dissolve_folder_moves_its_content_into_the_parent() {
+    let dir = temp_workspace("dissolve-folder");
+    let mut scrap = open_workspace(&dir);
+
+    let parent_id = scrap.create_folder(WORKSPACE_ID, "Parent".to_string()).unwrap();
+    let folder_id = scrap.create_folder(parent_id, "Loose".to_string()).unwrap();
+    let child_id = scrap.create_folder(folder_id, "Child".to_string()).unwrap();
+    let note_id = scrap
+        .create_note(folder_id, "Note".to_string(), "text".to_string())
+        .unwrap();
+    let nested_note_id = scrap
+        .create_note(child_id, "Nested".to_string(), "text".to_string())
+        .unwrap();
+
+    scrap.dissolve_folder(folder_id).unwrap();
+
+    let parent_dir = PathBuf::from(format!("parent____{}", parent_id));
+    assert!(!dir.join(&parent_dir).join(format!("loose____{}", folder_id)).exists());
+    assert!(matches!(
+        scrap.list_children(folder_id),
+        Err(ScrapError::FolderNotFound(_))
+    ));
+
+    let note = scrap.get_note(note_id).unwrap();
+    assert_eq!(note.folder_id, parent_id);
+    assert_eq!(note.relative_path, parent_dir.join(format!("note____{}.txt", note_id)));
+
+    let child = scrap.list_children(child_id).unwrap();
+    assert_eq!(child.parent_id, parent_id);
+    assert_eq!(child.relative_path, parent_dir.join(format!("child____{}", child_id)));
+
+    // Notes deeper down move along with their folder
+    let nested_note = scrap.get_note(nested_note_id).unwrap();
+    assert_eq!(nested_note.folder_id, child_id);
+    assert!(dir.join(&nested_note.relative_path).is_file());
+
+    // Nothing went to the trash
+    assert!(scrap.list_trash().unwrap().is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn plain_directories_keep_their_id_without_metadata() {
+    let dir = temp_workspace("plain-directories");
+    fs::create_dir_all(dir.join("plain").join("inner")).unwrap();
+
+    let tree = open_workspace(&dir).tree().unwrap();
+    let (folder_id, inner_id) = (tree.folders[0].id, tree.folders[0].folders[0].id);
+
+    // Syncing doesn't write into the directories, the IDs come back from the index cache
+    let tree = open_workspace(&dir).tree().unwrap();
+    assert_eq!(
+        (tree.folders[0].id, tree.folders[0].folders[0].id),
+        (folder_id, inner_id)
+    );
+    assert!(!dir.join("plain").join("_metadata.txt").exists());
+    assert!(!dir.join("plain").join("inner").join("_metadata.txt").exists());
+
+    // Moving the directory writes the metadata of every folder inside it, the cache only knows the old paths
+    let mut scrap = open_workspace(&dir);
+    let target_id = scrap.create_folder(WORKSPACE_ID, "Target".to_string()).unwrap();
+    scrap.move_folder(folder_id, target_id).unwrap();
+
+    let folder_dir = dir.join(format!("target____{}", target_id)).join("plain");
+    assert!(folder_dir.join("_metadata.txt").exists());
+    assert!(folder_dir.join("inner").join("_metadata.txt").exists());
+
+    let scrap = open_workspace(&dir);
+    assert_eq!(scrap.list_children(folder_id).unwrap().parent_id, target_id);
+    assert_eq!(scrap.list_children(inner_id).unwrap().parent_id, folder_id);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn trashed_plain_directory_is_restored_with_its_id() {
+    let dir = temp_workspace("trash-plain-directory");
+    fs::create_dir_all(dir.join("plain").join("inner")).unwrap();
+
+    let mut scrap = open_workspace(&dir);
+    let tree = scrap.tree().unwrap();
+    let (folder_id, inner_id) = (tree.folders[0].id, tree.folders[0].folders[0].id);
+
+    scrap.remove_folder(folder_id).unwrap();
+    assert_eq!(scrap.restore_folder(folder_id).unwrap(), WORKSPACE_ID);
+
+    assert_eq!(scrap.list_children(inner_id).unwrap().parent_id, folder_id);
+    assert!(dir.join("plain").join("_metadata.txt").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
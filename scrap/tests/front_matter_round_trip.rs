@@ -0,0 +1,374 @@
+mod common;
+
+use common::{WORKSPACE_ID, open_workspace, temp_workspace};
+use scrap::Scrap;
+
+use std::fs;
+use std::time::{Duration, SystemTime};
+use uuid::uuid;
+
+/// Names that broke or were ambiguous in headers before values were escaped
+const NAMES: &[&str] = &[
+    "Plain title",
+    "Café crème brûlée",
+    "日本語のノート",
+    "한국어 제목",
+    "Ελληνικά και русский",
+    "עברית ועربية",
+    "e\u{301} with a combining accent",
+    "🦀 emoji 👩‍👩‍👧 sequences",
+    "Say \"hi\"",
+    "\"",
+    "\"\"",
+    "back\\slash",
+    "trailing backslash \\",
+    "\\\"",
+    "\\n is not a line break",
+    "line\nbreak",
+    "carriage\r\nreturn",
+    "tab\there",
+    "  padded  ",
+    "colon: value",
+    "---",
+    "[\"not\", \"a list\"]",
+    "",
+];
+
+#[test]
+fn note_titles_round_trip() {
+    let dir = temp_workspace("note-titles");
+
+    let mut ids = Vec::new();
+    {
+        let mut scrap = open_workspace(&dir);
+
+        for name in NAMES {
+            let id = scrap
+                .create_note(WORKSPACE_ID, name.to_string(), "text".to_string())
+                .unwrap();
+            ids.push(id);
+        }
+    }
+
+    let mut scrap = open_workspace(&dir);
+    for (id, name) in ids.iter().zip(NAMES) {
+        assert_eq!(scrap.get_note(*id).unwrap().title, *name);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn renamed_note_titles_round_trip() {
+    let dir = temp_workspace("renamed-titles");
+
+    let mut ids = Vec::new();
+    {
+        let mut scrap = open_workspace(&dir);
+
+        for name in NAMES {
+            let id = scrap
+                .create_note(WORKSPACE_ID, "Untitled".to_string(), "text".to_string())
+                .unwrap();
+            scrap.update_note_body(id, format!("Body of {}", name)).unwrap();
+            scrap.save_note(id).unwrap();
+            scrap.rename_note(id, name.to_string()).unwrap();
+            ids.push(id);
+        }
+    }
+
+    let mut scrap = open_workspace(&dir);
+    for (id, name) in ids.iter().zip(NAMES) {
+        let note = scrap.get_note(*id).unwrap();
+
+        assert_eq!(note.title, *name);
+        assert_eq!(note.body, format!("Body of {}", name));
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn folder_names_round_trip() {
+    let dir = temp_workspace("folder-names");
+
+    // Folders without a display name are named after their directory
+    let names: Vec<&str> = NAMES.iter().copied().filter(|name| !name.is_empty()).collect();
+
+    let mut ids = Vec::new();
+    {
+        let mut scrap = open_workspace(&dir);
+
+        for name in &names {
+            ids.push(scrap.create_folder(WORKSPACE_ID, name.to_string()).unwrap());
+        }
+    }
+
+    let scrap = open_workspace(&dir);
+    for (id, name) in ids.iter().zip(&names) {
+        assert_eq!(scrap.list_children(*id).unwrap().display_name, *name);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn tags_round_trip() {
+    let dir = temp_workspace("tags");
+    let tags = ["über/straße", "日本語", "back\\slash", "emoji/🦀"];
+
+    let id;
+    {
+        let mut scrap = open_workspace(&dir);
+
+        id = scrap
+            .create_note(WORKSPACE_ID, "Tagged".to_string(), "text".to_string())
+            .unwrap();
+        for tag in tags {
+            scrap.add_tag(id, tag).unwrap();
+        }
+        scrap.save_note(id).unwrap();
+    }
+
+    let mut scrap = open_workspace(&dir);
+    assert_eq!(scrap.get_note(id).unwrap().tags, tags);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn legacy_headers_are_read_with_escapes() {
+    let dir = temp_workspace("legacy-headers");
+    let ids = [
+        uuid!("1c7a9e5b-3d2f-4b8a-9e6c-0a1b2c3d4e5f"),
+        uuid!("2d8b0f6c-4e3a-4c9b-8f7d-1b2c3d4e5f60"),
+        uuid!("3e9c1a7d-5f4b-4dac-9a8e-2c3d4e5f6071"),
+    ];
+
+    // Written before values were escaped, quotes and backslashes inside values were left as they were
+    let titles = [r#"Say "hi""#, r"C:\Users\me", r"a\nb"];
+    for (id, title) in ids.iter().zip(titles) {
+        let content = format!("---\nid: \"{}\"\ntitle: \"{}\"\n---\nbody", id, title);
+        fs::write(dir.join(format!("{}.txt", id)), content).unwrap();
+    }
+
+    // Escape sequences in old values are read as escapes, other backslashes are kept
+    let expected = ["Say \"hi\"", "C:\\Users\\me", "a\nb"];
+    {
+        let mut scrap = open_workspace(&dir);
+
+        for (id, title) in ids.iter().zip(expected) {
+            assert_eq!(scrap.get_note(*id).unwrap().title, title);
+
+            scrap.add_tag(*id, "migrated").unwrap();
+            scrap.save_note(*id).unwrap();
+        }
+    }
+
+    // Saved again with escaping, the values read back the same
+    let mut scrap = open_workspace(&dir);
+    for (id, title) in ids.iter().zip(expected) {
+        assert_eq!(scrap.get_note(*id).unwrap().title, title);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn hand_written_tags_are_kept() {
+    let dir = temp_workspace("hand-written-tags");
+    let id = uuid!("5b0e7d4c-2f61-4c0a-8d3e-9a7b6c5d4e3f");
+
+    // A YAML block list, with tags that can't be normalized
+    let content = format!(
+        "---\nid: \"{}\"\ntitle: \"Hand written\"\ntags:\n  - Project/Scrap\n  - \"#Reading\"\n  - a//b\n  - \"x, y\"\nsource: \"web\"\n---\nbody",
+        id
+    );
+    fs::write(dir.join("hand.txt"), content).unwrap();
+
+    let tags = ["project/scrap", "reading", "a//b", "x, y"];
+    {
+        let mut scrap = open_workspace(&dir);
+        assert_eq!(scrap.get_note(id).unwrap().tags, tags);
+
+        scrap.remove_tag(id, "a//b").unwrap();
+        scrap.save_note(id).unwrap();
+    }
+
+    let note = open_workspace(&dir).get_note(id).unwrap();
+
+    assert_eq!(note.tags, ["project/scrap", "reading", "x, y"]);
+    assert_eq!(note.properties, [("source".to_string(), "web".to_string())]);
+
+    let bare_id = uuid!("6c1f8e5d-3a72-4d1b-9e4f-0b8c7d6e5f40");
+    let broken_id = uuid!("7d2a9f6e-4b83-4e2c-8f5a-1c9d8e7f6051");
+
+    // YAML flow lists with bare items, and one that can't be read
+    let bare = format!(
+        "---\nid: \"{}\"\ntitle: \"Bare\"\ntags: [work, personal, 'a, b']\n---\nbody",
+        bare_id
+    );
+    let broken = format!(
+        "---\nid: \"{}\"\ntitle: \"Broken\"\ntags: [work, \"open\n---\nbody",
+        broken_id
+    );
+    fs::write(dir.join("bare.txt"), bare).unwrap();
+    fs::write(dir.join("broken.txt"), broken).unwrap();
+
+    {
+        let mut scrap = open_workspace(&dir);
+        assert_eq!(scrap.get_note(bare_id).unwrap().tags, ["work", "personal", "a, b"]);
+        assert!(scrap.get_note(broken_id).unwrap().tags.is_empty());
+
+        for id in [bare_id, broken_id] {
+            scrap.add_tag(id, "synced").unwrap();
+            scrap.save_note(id).unwrap();
+        }
+    }
+
+    let mut scrap = open_workspace(&dir);
+    assert_eq!(
+        scrap.get_note(bare_id).unwrap().tags,
+        ["work", "personal", "a, b", "synced"]
+    );
+
+    // The unreadable line is written back as it was
+    let saved = fs::read_to_string(dir.join("broken.txt")).unwrap();
+    assert!(saved.contains("\ntags: [work, \"open\n"));
+    assert_eq!(scrap.get_note(broken_id).unwrap().tags, ["synced"]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn hand_written_unquoted_values_are_read() {
+    let dir = temp_workspace("unquoted-values");
+    let id = uuid!("9f4c2b8e-6d15-4a3f-b7e9-3e1f0a9b8c72");
+    let folder_id = uuid!("a05d3c9f-7e26-4b40-88fa-4f2a1b0c9d83");
+
+    let note = format!("---\nid: {}\ntitle: My Plan\ntype: markdown\n---\nbody", id);
+    fs::write(dir.join("plan.txt"), note).unwrap();
+
+    fs::create_dir(dir.join("foo")).unwrap();
+    let folder = format!("id: {}\ndisplay-name: Foo Bar\n", folder_id);
+    fs::write(dir.join("foo").join("_metadata.txt"), folder).unwrap();
+
+    {
+        let mut scrap = open_workspace(&dir);
+
+        let note = scrap.get_note(id).unwrap();
+        assert_eq!((note.title.as_str(), note.file_type.as_str()), ("My Plan", "markdown"));
+        assert_eq!(scrap.list_children(folder_id).unwrap().display_name, "Foo Bar");
+
+        scrap.add_tag(id, "synced").unwrap();
+        scrap.save_note(id).unwrap();
+    }
+
+    // Saving writes the values quoted
+    let note = open_workspace(&dir).get_note(id).unwrap();
+    assert_eq!((note.title.as_str(), note.file_type.as_str()), ("My Plan", "markdown"));
+    assert!(note.properties.is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn sync_only_adds_the_id_line() {
+    let dir = temp_workspace("sync-id-line");
+
+    // Header lines this app doesn't write itself, with its own line breaks, and a note without front matter
+    let files = [
+        (
+            "plan.txt",
+            "---\ntitle: My Plan\ntype: markdown\ntags: [work, personal]\n# kept as is\nsource:  web \n---\nbody\n",
+        ),
+        (
+            "windows.txt",
+            "\u{feff}---\r\ntitle: \"Windows\"\r\nstatus: draft\r\n---\r\nbody\r\n",
+        ),
+        ("plain.txt", "just a body"),
+    ];
+    for (name, content) in files {
+        fs::write(dir.join(name), content).unwrap();
+    }
+
+    let mut scrap = open_workspace(&dir);
+    let ids: Vec<String> = scrap
+        .list_notes()
+        .unwrap()
+        .iter()
+        .map(|note| note.id.to_string())
+        .collect();
+
+    let expected_files = [
+        (
+            "plan.txt",
+            "---\nid: \"{}\"\ntitle: My Plan\ntype: markdown\ntags: [work, personal]\n# kept as is\nsource:  web \n---\nbody\n",
+        ),
+        (
+            "windows.txt",
+            "\u{feff}---\r\nid: \"{}\"\r\ntitle: \"Windows\"\r\nstatus: draft\r\n---\r\nbody\r\n",
+        ),
+        ("plain.txt", "---\nid: \"{}\"\n---\njust a body"),
+    ];
+    for (name, expected) in expected_files {
+        let saved = fs::read_to_string(dir.join(name)).unwrap();
+
+        let id = ids.iter().find(|id| saved.contains(id.as_str())).unwrap();
+        assert_eq!(saved, expected.replace("{}", id));
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn line_endings_round_trip() {
+    let dir = temp_workspace("line-endings");
+    let id = uuid!("0f5c3a52-8a4e-4d36-9a8e-3c1f1d2b7e10");
+
+    // Written on Windows with a byte order mark, and without a line break after the body
+    let content = format!(
+        "\u{feff}---\r\nid: \"{}\"\r\ntitle: \"Windows\"\r\ntype: \"text\"\r\n---\r\nfirst\r\nsecond",
+        id
+    );
+    fs::write(dir.join("windows.txt"), &content).unwrap();
+
+    {
+        let mut scrap = open_workspace(&dir);
+        assert_eq!(scrap.get_note(id).unwrap().body, "first\r\nsecond");
+
+        scrap.add_tag(id, "synced").unwrap();
+        scrap.save_note(id).unwrap();
+    }
+
+    let saved = fs::read_to_string(dir.join("windows.txt")).unwrap();
+    assert_eq!(
+        saved,
+        format!(
+            "\u{feff}---\r\nid: \"{}\"\r\ntitle: \"Windows\"\r\ntype: \"text\"\r\ntags: [\"synced\"]\r\n---\r\nfirst\r\nsecond",
+            id
+        )
+    );
+
+    // Files modified in the last seconds aren't cached, an older one is cached by the next sync so the
+    // one after it with lazy bodies reads the note from the index cache
+    let file = fs::File::options().write(true).open(dir.join("windows.txt")).unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(60)).unwrap();
+    drop(file);
+    open_workspace(&dir);
+
+    // Removing the tag again writes back the exact original bytes
+    {
+        let mut scrap = Scrap::new();
+        scrap.set_workspace(&dir).unwrap();
+        scrap.set_lazy_bodies(true).unwrap();
+        scrap.sync_workspace().unwrap();
+
+        scrap.remove_tag(id, "synced").unwrap();
+        scrap.save_note(id).unwrap();
+    }
+
+    assert_eq!(fs::read(dir.join("windows.txt")).unwrap(), content.as_bytes());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::{open_workspace, temp_workspace};
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use uuid::uuid;
+
+/// Sets the modification time outside the window in which files are left out of the cache.
+fn set_modified(path: &Path, modified: SystemTime) {
+    let file = fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(modified).unwrap();
+}
+
+#[test]
+fn unchanged_files_are_not_parsed_again() {
+    let dir = temp_workspace("cache-unchanged");
+    let id = uuid!("8e3b0a7f-5c94-4f3d-9a6b-2d0e9f8a7162");
+    let path = dir.join("cached.txt");
+    let modified = SystemTime::now() - Duration::from_secs(60);
+
+    fs::write(&path, format!("---\nid: \"{}\"\ntitle: \"Alpha\"\n---\nfirst body", id)).unwrap();
+    set_modified(&path, modified);
+
+    // The first sync caches the file
+    assert_eq!(open_workspace(&dir).get_note(id).unwrap().title, "Alpha");
+
+    // Same size and modification time, so the next sync takes the front matter from the cache
+    // and only reads the body from the file
+    fs::write(&path, format!("---\nid: \"{}\"\ntitle: \"Omega\"\n---\nother body", id)).unwrap();
+    set_modified(&path, modified);
+
+    let note = open_workspace(&dir).get_note(id).unwrap();
+    assert_eq!(note.title, "Alpha");
+    assert_eq!(note.body, "other body");
+
+    // A changed size means the file is parsed again
+    fs::write(
+        &path,
+        format!("---\nid: \"{}\"\ntitle: \"Omega\"\n---\nlonger body", id),
+    )
+    .unwrap();
+    set_modified(&path, modified);
+
+    let note = open_workspace(&dir).get_note(id).unwrap();
+    assert_eq!(note.title, "Omega");
+    assert_eq!(note.body, "longer body");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use common::{WORKSPACE_ID, open_workspace, temp_workspace};
+use scrap::ScrapError;
+
+use std::fs;
+
+#[test]
+fn rename_note_renames_file_and_front_matter() {
+    let dir = temp_workspace("rename-note");
+    let mut scrap = open_workspace(&dir);
+
+    let id = scrap
+        .create_note(WORKSPACE_ID, "Draft".to_string(), "text".to_string())
+        .unwrap();
+    let old_path = scrap.get_note(id).unwrap().relative_path;
+
+    scrap.rename_note(id, "Final plan".to_string()).unwrap();
+
+    let note = scrap.get_note(id).unwrap();
+    assert_eq!(note.title, "Final plan");
+    assert_eq!(
+        note.relative_path.to_string_lossy(),
+        format!("final-plan____{}.txt", id)
+    );
+
+    assert!(!dir.join(&old_path).exists());
+    let content = fs::read_to_string(dir.join(&note.relative_path)).unwrap();
+    assert!(content.contains("title: \"Final plan\"\n"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn failed_rename_moves_the_file_back() {
+    let dir = temp_workspace("rename-note-rollback");
+    let mut scrap = open_workspace(&dir);
+
+    let id = scrap
+        .create_note(WORKSPACE_ID, "Draft".to_string(), "text".to_string())
+        .unwrap();
+    let old_path = scrap.get_note(id).unwrap().relative_path;
+
+    // An unclosed front matter can't be rewritten, so the rename fails after the file was moved
+    let content = format!("---\nid: \"{}\"\ntitle: \"Draft\"\nbody", id);
+    fs::write(dir.join(&old_path), &content).unwrap();
+
+    assert!(scrap.rename_note(id, "Final".to_string()).is_err());
+
+    assert_eq!(fs::read_to_string(dir.join(&old_path)).unwrap(), content);
+    assert!(!dir.join(format!("final____{}.txt", id)).exists());
+
+    let note = scrap.get_note(id).unwrap();
+    assert_eq!(note.title, "Draft");
+    assert_eq!(note.relative_path, old_path);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn trashed_note_is_restored_from_the_manifest() {
+    let dir = temp_workspace("trash-note");
+    let mut scrap = open_workspace(&dir);
+
+    let folder_id = scrap.create_folder(WORKSPACE_ID, "Inbox".to_string()).unwrap();
+    let id = scrap
+        .create_note(folder_id, "Draft".to_string(), "text".to_string())
+        .unwrap();
+    scrap.update_note_body(id, "keep me".to_string()).unwrap();
+    scrap.save_note(id).unwrap();
+    let path = scrap.get_note(id).unwrap().relative_path;
+
+    scrap.remove_note(id).unwrap();
+
+    assert!(!dir.join(&path).exists());
+    assert!(matches!(scrap.get_note(id), Err(ScrapError::NoteNotFound(_))));
+
+    let trash = scrap.list_trash().unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!((trash[0].id, trash[0].original_folder_id), (id, folder_id));
+    assert_eq!(trash[0].original_path, path);
+
+    let manifest = fs::read_to_string(dir.join(".trash/_manifest.txt")).unwrap();
+    assert!(manifest.contains(&format!("id: \"{}\"\nkind: \"note\"\n", id)));
+
+    // The trash is read from the manifest, so it survives a new session
+    let mut scrap = open_workspace(&dir);
+    assert_eq!(scrap.restore_note(id).unwrap(), folder_id);
+
+    let note = scrap.get_note(id).unwrap();
+    assert_eq!(note.relative_path, path);
+    assert_eq!(note.body, "keep me");
+    assert!(scrap.list_trash().unwrap().is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn note_is_restored_into_the_root_when_its_folder_is_gone() {
+    let dir = temp_workspace("trash-note-orphan");
+    let mut scrap = open_workspace(&dir);
+
+    let folder_id = scrap.create_folder(WORKSPACE_ID, "Inbox".to_string()).unwrap();
+    let id = scrap
+        .create_note(folder_id, "Draft".to_string(), "text".to_string())
+        .unwrap();
+
+    scrap.remove_note(id).unwrap();
+    scrap.remove_folder(folder_id).unwrap();
+
+    assert_eq!(scrap.restore_note(id).unwrap(), WORKSPACE_ID);
+    assert!(dir.join(format!("draft____{}.txt", id)).is_file());
+    assert_eq!(scrap.get_note(id).unwrap().folder_id, WORKSPACE_ID);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn note_stays_indexed_when_it_cant_be_trashed() {
+    let dir = temp_workspace("trash-note-failure");
+    let mut scrap = open_workspace(&dir);
+
+    let id = scrap
+        .create_note(WORKSPACE_ID, "Draft".to_string(), "text".to_string())
+        .unwrap();
+    let path = scrap.get_note(id).unwrap().relative_path;
+
+    // Another item already has the name the note would get in the trash
+    fs::write(dir.join(".trash").join(&path), "").unwrap();
+
+    assert!(scrap.remove_note(id).is_err());
+
+    assert!(dir.join(&path).is_file());
+    assert_eq!(scrap.get_note(id).unwrap().relative_path, path);
+    assert!(scrap.list_notes().unwrap().iter().any(|note| note.id == id));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn retention_periods_that_overflow_are_rejected() {
+    let dir = temp_workspace("trash-retention");
+    let mut scrap = open_workspace(&dir);
+
+    let id = scrap
+        .create_note(WORKSPACE_ID, "Draft".to_string(), "text".to_string())
+        .unwrap();
+    scrap.remove_note(id).unwrap();
+
+    let days = u64::MAX / 1000;
+    assert!(matches!(
+        scrap.set_trash_retention_days(Some(days)),
+        Err(ScrapError::InvalidRetentionDays(_))
+    ));
+
+    // A hand-edited settings file isn't checked until the trash is purged
+    fs::write(
+        dir.join("_workspace.txt"),
+        format!("---\ntrash-retention-days: \"{}\"\n---\n", days),
+    )
+    .unwrap();
+    let mut scrap = open_workspace(&dir);
+
+    assert!(matches!(
+        scrap.purge_expired_trash(),
+        Err(ScrapError::InvalidRetentionDays(_))
+    ));
+    assert_eq!(scrap.list_trash().unwrap().len(), 1);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
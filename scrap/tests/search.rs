@@ -0,0 +1,68 @@
+mod common;
+
+use common::{WORKSPACE_ID, open_workspace, temp_workspace};
+
+use std::fs;
+
+#[test]
+fn single_cjk_character_matches_inside_longer_words() {
+    let dir = temp_workspace("search-cjk");
+    let mut scrap = open_workspace(&dir);
+
+    let id = scrap
+        .create_note(WORKSPACE_ID, "Minutes".to_string(), "text".to_string())
+        .unwrap();
+    scrap
+        .update_note_body(id, "오늘 회의록을 정리했다".to_string())
+        .unwrap();
+    scrap.save_note(id).unwrap();
+
+    for query in ["회", "록", "회의록"] {
+        let hits = scrap.search(query, 0, 10).unwrap();
+        assert_eq!(hits.len(), 1, "query '{}'", query);
+        assert_eq!(hits[0].id, id);
+    }
+
+    assert!(scrap.search("회사", 0, 10).unwrap().is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn lazy_bodies_are_read_for_the_snippets_of_returned_hits() {
+    let dir = temp_workspace("search-lazy-snippets");
+
+    {
+        let mut scrap = open_workspace(&dir);
+
+        for title in ["First", "Second", "Third"] {
+            let id = scrap
+                .create_note(WORKSPACE_ID, title.to_string(), "text".to_string())
+                .unwrap();
+            scrap
+                .update_note_body(id, format!("{} note about the garden", title))
+                .unwrap();
+            scrap.save_note(id).unwrap();
+        }
+    }
+
+    let mut scrap = scrap::Scrap::new();
+    scrap.set_workspace(&dir).unwrap();
+    scrap.set_lazy_bodies(true).unwrap();
+    scrap.sync_workspace().unwrap();
+
+    let all_hits = scrap.search("garden", 0, 10).unwrap();
+    assert_eq!(all_hits.len(), 3);
+
+    for hit in &all_hits {
+        assert_eq!(hit.snippet, format!("{} note about the garden", hit.title));
+    }
+
+    // Pages continue where the previous one stopped
+    let page = scrap.search("garden", 1, 1).unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].id, all_hits[1].id);
+    assert!(scrap.search("garden", 3, 10).unwrap().is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}